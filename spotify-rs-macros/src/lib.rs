@@ -144,7 +144,7 @@ fn field_documentation(field_name: &str, name: &str) -> Option<proc_macro2::Toke
         }
 
         "resume_point" => format_some!(
-            "The user's latest position in the {name}.\n\nNote: this field is only available if the user is authorised with the `user-read-playback-position` scope."
+            "The user's latest position in the {name}.\n\nNote: this field is only available if the user is authorised with the `user-read-playback-position` scope. Otherwise (e.g. when using the Client Credentials flow), it's `None`, rather than a position of `0`."
         ),
 
         _ => None,
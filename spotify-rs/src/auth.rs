@@ -1,4 +1,9 @@
-use std::{collections::HashSet, fmt::Debug, time::Duration};
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    time::Duration,
+};
 
 use chrono::{DateTime, Utc};
 use oauth2::{
@@ -11,7 +16,7 @@ pub trait AuthenticationState: private::Sealed {}
 impl AuthenticationState for Token {}
 impl AuthenticationState for Unauthenticated {}
 
-pub trait AuthFlow: private::Sealed + Debug {}
+pub trait AuthFlow: private::Sealed + private::TokenOwner + Debug {}
 impl AuthFlow for AuthCodeFlow {}
 impl AuthFlow for AuthCodePkceFlow {}
 impl AuthFlow for ClientCredsFlow {}
@@ -54,13 +59,26 @@ mod private {
     impl Sealed for super::AuthCodePkceFlow {}
     impl Sealed for super::ClientCredsFlow {}
     impl Sealed for super::UnknownFlow {}
+
+    // Whether or not the tokens of an auth flow are issued on behalf of a user.
+    pub trait TokenOwner {
+        const USER: bool = true;
+    }
+
+    impl TokenOwner for super::AuthCodeFlow {}
+    impl TokenOwner for super::AuthCodePkceFlow {}
+    impl TokenOwner for super::UnknownFlow {}
+    impl TokenOwner for super::ClientCredsFlow {
+        const USER: bool = false;
+    }
 }
 
 /// A list of (unique) scopes. You don't usually have to interact
 /// with it directly, the conversion should happen implicitly, with the exception of
 /// the [`from_refresh_token`](Client::from_refresh_token) function.
 ///
-/// In such cases, you should just call [into](Into::into) on your list of scopes.
+/// In such cases, you should just call [into](Into::into) on your list of scopes,
+/// which can be either strings or [`Scope`]s.
 #[derive(Clone, Debug, Default)]
 pub struct Scopes(pub(crate) HashSet<oauth2::Scope>);
 
@@ -96,6 +114,106 @@ impl Scopes {
     }
 }
 
+// Define the `Scope` enum, along with its conversions from and to strings.
+macro_rules! scopes {
+    ($($(#[$meta:meta])* $variant:ident => $value:literal),+ $(,)?) => {
+        /// An [authorisation scope](https://developer.spotify.com/documentation/web-api/concepts/scopes).
+        ///
+        /// It can be used anywhere a list of scopes is expected, as it can be
+        /// converted into a string. Scopes that aren't known by the crate (e.g.
+        /// ones added by Spotify in the future) can be represented by
+        /// [`Scope::Other`], so the typed scopes can be mixed with raw strings.
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum Scope {
+            $($(#[$meta])* $variant,)+
+            /// Any other scope, not (yet) known by the crate.
+            Other(String),
+        }
+
+        impl Scope {
+            /// Get the scope as it's used by Spotify, e.g. `user-read-private`.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Other(scope) => scope,
+                }
+            }
+        }
+
+        impl From<&str> for Scope {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)+
+                    other => Self::Other(other.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+scopes! {
+    /// Write access to user-provided images.
+    UgcImageUpload => "ugc-image-upload",
+    /// Read access to a user's player state.
+    UserReadPlaybackState => "user-read-playback-state",
+    /// Write access to a user's playback state.
+    UserModifyPlaybackState => "user-modify-playback-state",
+    /// Read access to a user's currently playing content.
+    UserReadCurrentlyPlaying => "user-read-currently-playing",
+    /// Remote control playback of Spotify. Only used by the iOS and Android SDKs.
+    AppRemoteControl => "app-remote-control",
+    /// Control playback of a Spotify track. Only used by the Web Playback SDK.
+    Streaming => "streaming",
+    /// Read access to a user's private playlists.
+    PlaylistReadPrivate => "playlist-read-private",
+    /// Include collaborative playlists when requesting a user's playlists.
+    PlaylistReadCollaborative => "playlist-read-collaborative",
+    /// Write access to a user's private playlists.
+    PlaylistModifyPrivate => "playlist-modify-private",
+    /// Write access to a user's public playlists.
+    PlaylistModifyPublic => "playlist-modify-public",
+    /// Write access to the list of artists and other users a user follows.
+    UserFollowModify => "user-follow-modify",
+    /// Read access to the list of artists and other users a user follows.
+    UserFollowRead => "user-follow-read",
+    /// Read access to a user's playback position in a content.
+    UserReadPlaybackPosition => "user-read-playback-position",
+    /// Read access to a user's top artists and tracks.
+    UserTopRead => "user-top-read",
+    /// Read access to a user's recently played tracks.
+    UserReadRecentlyPlayed => "user-read-recently-played",
+    /// Write access to a user's library.
+    UserLibraryModify => "user-library-modify",
+    /// Read access to a user's library.
+    UserLibraryRead => "user-library-read",
+    /// Read access to a user's email address.
+    UserReadEmail => "user-read-email",
+    /// Read access to a user's subscription details (type of user account).
+    UserReadPrivate => "user-read-private",
+}
+
+impl From<String> for Scope {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<Scope> for String {
+    fn from(value: Scope) -> Self {
+        match value {
+            Scope::Other(scope) => scope,
+            scope => scope.as_str().to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// An OAuth2 token.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Token {
@@ -230,6 +348,26 @@ impl Token {
         Utc::now() >= self.expires_at
     }
 
+    // Get a hash identifying whose token this is, used to keep the responses
+    // of different users apart in an `ETagCache`, which has to stay the same
+    // when the token is refreshed. The refresh token is used when present, as
+    // it outlives the access tokens obtained with it. Tokens that aren't issued
+    // on behalf of a user (i.e. client credentials) can only access the same
+    // public data, so they're all scoped to the client ID.
+    pub(crate) fn cache_scope<F: AuthFlow>(&self, client_id: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        if F::USER {
+            self.refresh_secret()
+                .unwrap_or_else(|| self.secret())
+                .hash(&mut hasher);
+        } else {
+            client_id.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
     /// Returns `true` if a refresh token is present.
     pub fn is_refreshable(&self) -> bool {
         self.refresh_token.is_some()
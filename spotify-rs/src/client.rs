@@ -1,6 +1,7 @@
 use std::{
     fmt::Debug,
     sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 
 use oauth2::{
@@ -12,24 +13,65 @@ use oauth2::{
     AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge, RedirectUrl,
     RefreshToken, StandardRevocableToken, TokenUrl,
 };
-use reqwest::{header::CONTENT_LENGTH, Method, Url};
+use reqwest::{
+    header::{HeaderValue, CONTENT_LENGTH, ETAG, IF_NONE_MATCH, RETRY_AFTER, USER_AGENT},
+    Method, StatusCode, Url,
+};
 use serde::{
     de::{value::BytesDeserializer, DeserializeOwned, IntoDeserializer},
     Serialize,
 };
+use tokio::sync::Semaphore;
 use tracing::info;
 
 use crate::{
+    Raw,
     auth::{
-        AuthCodeFlow, AuthCodePkceFlow, AuthFlow, AuthenticationState, ClientCredsFlow, Scopes,
-        Token, Unauthenticated, UnknownFlow,
+        AuthCodeFlow, AuthCodePkceFlow, AuthFlow, AuthenticationState, Authorised, ClientCredsFlow,
+        Scopes, Token, Unauthenticated, UnknownFlow,
     },
-    error::{Error, Result, SpotifyError},
+    error::{Error, NetworkErrorKind, Result, SpotifyError},
 };
 
+mod cache;
+mod config;
+mod metrics;
+#[cfg(test)]
+pub(crate) mod mock;
+mod rate_limit;
+mod save_state;
+mod transport;
+
+pub use cache::{CachedResponse, ETagCache, MemoryETagCache};
+pub use config::ClientConfig;
+pub use metrics::MetricsRecorder;
+pub use rate_limit::RateLimitInfo;
+pub use transport::{Transport, TransportFuture};
+
+use rate_limit::RateLimitGate;
+use save_state::SaveStateCache;
+
 const AUTHORISATION_URL: &str = "https://accounts.spotify.com/authorize";
 const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
 pub(crate) const API_URL: &str = "https://api.spotify.com/v1";
+// How long to delay requests after being rate limited, if Spotify doesn't say.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+// The delay before the first retry of a request, doubled for every retry after.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+// The longest a retry is ever delayed, no matter how many retries came before.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+// The most times a request can be retried, regardless of the configuration.
+const MAX_RETRIES: u32 = 10;
+const DEFAULT_USER_AGENT: &str = concat!("spotify-rs/", env!("CARGO_PKG_VERSION"));
+
+/// The environment variable holding the client ID, used by `from_env`.
+pub const CLIENT_ID_VAR: &str = "SPOTIFY_CLIENT_ID";
+/// The environment variable holding the client secret, used by `from_env`.
+pub const CLIENT_SECRET_VAR: &str = "SPOTIFY_CLIENT_SECRET";
+/// The environment variable holding the redirect URI, used by `from_env`.
+pub const REDIRECT_URI_VAR: &str = "SPOTIFY_REDIRECT_URI";
+/// The environment variable holding a (cached) refresh token, used by `from_env`.
+pub const REFRESH_TOKEN_VAR: &str = "SPOTIFY_REFRESH_TOKEN";
 
 pub(crate) type OAuthClient = oauth2::Client<
     BasicErrorResponse,
@@ -40,6 +82,120 @@ pub(crate) type OAuthClient = oauth2::Client<
     BasicRevocationErrorResponse,
 >;
 
+/// Build the URL to which a user should be redirected to authorise the app,
+/// without creating a client.
+///
+/// This is useful when the authorisation is handled separately (e.g. in a
+/// browser you manage), and only the URL is needed. Once the user authorises
+/// the app, they are redirected to `redirect_uri`, along with a `code` and the
+/// given `state` parameter. If `show_dialog` is `true`, the user will be asked
+/// to authorise the app again, even if they've already done so.
+///
+/// The `state` is URL-encoded, and should be a random string (e.g. one returned
+/// by [`generate_state`]) that's later checked using [`parse_auth_callback`],
+/// for CSRF protection.
+///
+/// For the Authorisation Code with PKCE Flow, `code_challenge` must be the
+/// (base64url encoded) SHA-256 challenge of the code verifier later used to
+/// request the access token.
+pub fn authorisation_url<S: Into<Scopes>>(
+    client_id: &str,
+    redirect_uri: &RedirectUrl,
+    scopes: S,
+    state: &str,
+    show_dialog: bool,
+    code_challenge: Option<&str>,
+) -> Url {
+    let scopes: Vec<String> = scopes.into().0.into_iter().map(|s| s.to_string()).collect();
+    let mut url = Url::parse(AUTHORISATION_URL).unwrap();
+
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", client_id)
+            .append_pair("redirect_uri", redirect_uri.as_str())
+            .append_pair("state", state);
+
+        if !scopes.is_empty() {
+            query.append_pair("scope", &scopes.join(" "));
+        }
+
+        if show_dialog {
+            query.append_pair("show_dialog", "true");
+        }
+
+        if let Some(code_challenge) = code_challenge {
+            query
+                .append_pair("code_challenge_method", "S256")
+                .append_pair("code_challenge", code_challenge);
+        }
+    }
+
+    url
+}
+
+/// Generate a cryptographically random `state` parameter, to be used with
+/// [`authorisation_url`].
+pub fn generate_state() -> String {
+    CsrfToken::new_random().secret().to_owned()
+}
+
+/// The parameters Spotify redirects the user with, after they authorised the app.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthCallback {
+    /// The authorisation code, which can be exchanged for an access token.
+    pub code: String,
+    /// The state parameter, which has been verified to match the expected one.
+    pub state: String,
+}
+
+/// Parse the URL the user was redirected to after authorising the app
+/// (or not), extracting the authorisation `code`.
+///
+/// The `state` parameter of the URL is verified to match `expected_state`
+/// (the one used to create the authorisation URL), returning an
+/// [`Error::InvalidStateParameter`] otherwise. If the user denied access
+/// (or another error occurred), an [`Error::AuthorisationDenied`] with the
+/// error returned by Spotify is returned.
+pub fn parse_auth_callback(redirect_url: &str, expected_state: &str) -> Result<AuthCallback> {
+    let url = Url::parse(redirect_url).map_err(|e| Error::Parse {
+        description: format!("Failed to parse the redirect URL: {e}"),
+    })?;
+
+    let mut code = None;
+    let mut state = None;
+    let mut error = None;
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "code" => code = Some(value.into_owned()),
+            "state" => state = Some(value.into_owned()),
+            "error" => error = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    if state.as_deref() != Some(expected_state) {
+        return Err(Error::InvalidStateParameter);
+    }
+
+    if let Some(error) = error {
+        return Err(Error::AuthorisationDenied { error });
+    }
+
+    let Some(code) = code else {
+        return Err(Error::Parse {
+            description: "The redirect URL doesn't contain a `code` parameter.".to_owned(),
+        });
+    };
+
+    Ok(AuthCallback {
+        code,
+        state: expected_state.to_owned(),
+    })
+}
+
 /// A client created using the Authorisation Code Flow.
 pub type AuthCodeClient<A> = Client<A, AuthCodeFlow>;
 
@@ -77,6 +233,248 @@ pub struct Client<A: AuthenticationState, F: AuthFlow> {
     pub(crate) oauth: OAuthClient,
     // The HTTP client.
     pub(crate) http: reqwest::Client,
+    // The cache used for conditional requests, if enabled.
+    pub(crate) etag_cache: Option<Arc<dyn ETagCache>>,
+    // The configuration of the client.
+    pub(crate) config: ClientConfig,
+    // Delays requests after Spotify responds with `429 Too Many Requests`.
+    pub(crate) rate_limit_gate: Arc<RateLimitGate>,
+    // The cache of whether or not tracks are saved, if enabled.
+    pub(crate) save_state_cache: Option<Arc<SaveStateCache>>,
+    // Limits the amount of requests in flight at once, if set.
+    pub(crate) concurrency_limit: Option<Arc<Semaphore>>,
+    // The transport used to send requests instead of the HTTP client, if set.
+    pub(crate) transport: Option<Arc<dyn Transport>>,
+    // Receives events about the requests sent, if set.
+    pub(crate) metrics: Option<Arc<dyn MetricsRecorder>>,
+}
+
+impl<A: AuthenticationState, F: AuthFlow> Client<A, F> {
+    /// Enable conditional requests, using the given cache to store responses
+    /// and their ETags.
+    ///
+    /// When a `GET` request is sent to a URL that has a cached response, the
+    /// cached response will be used if the data hasn't changed, instead of
+    /// downloading it again. Read more in [`ETagCache`].
+    pub fn with_etag_cache(mut self, cache: impl ETagCache + 'static) -> Self {
+        self.etag_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Cache whether or not tracks are saved in the current user's library
+    /// for the given duration, to avoid checking the same tracks repeatedly
+    /// with [`check_saved_tracks`](crate::endpoint::track::check_saved_tracks).
+    ///
+    /// Saving or removing tracks with [`save_tracks`](crate::endpoint::track::save_tracks)
+    /// and [`remove_saved_tracks`](crate::endpoint::track::remove_saved_tracks)
+    /// updates the cache immediately, but changes made elsewhere (e.g. in
+    /// the Spotify app) are only seen once the cached state expires.
+    pub fn with_save_state_cache(mut self, ttl: Duration) -> Self {
+        self.save_state_cache = Some(Arc::new(SaveStateCache::new(ttl)));
+        self
+    }
+
+    /// Limit the amount of requests the client (and its clones) sends at once,
+    /// waiting for a request to finish before sending another one when the
+    /// limit is reached.
+    ///
+    /// The limit is shared by every request, so it also caps the total when
+    /// several operations run concurrently (e.g. multiple paginations).
+    /// A limit of `0` is treated as `1`.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.concurrency_limit = Some(Arc::new(Semaphore::new(max_concurrency.max(1))));
+        self
+    }
+
+    /// Send requests to the Spotify API using the given [`Transport`], instead
+    /// of the client's HTTP client, e.g. to mock the API in tests.
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Report events about the requests sent by the client (and its clones)
+    /// to the given [`MetricsRecorder`], e.g. to monitor them.
+    pub fn with_metrics(mut self, metrics: impl MetricsRecorder + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Configure the client using the given [`ClientConfig`], replacing the
+    /// current configuration.
+    ///
+    /// Every setting is replaced, including the ones set by earlier calls to
+    /// the other `with_*` methods (e.g. [`with_market`](Self::with_market)),
+    /// which are reset to the defaults if they're `None` in the given
+    /// configuration. To override some of the settings of a configuration,
+    /// call this method first, and then the other methods, e.g.
+    /// `spotify.with_config(config).with_market("GB")`.
+    pub fn with_config(mut self, config: ClientConfig) -> Self {
+        self.config = config;
+        self.http = self.config.build_http();
+        self
+    }
+
+    /// Get the current configuration of the client.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Get the rate limit information of the latest response that included
+    /// any, even if the request succeeded.
+    ///
+    /// It's shared between the clones of a client, and is `None` if no
+    /// response included rate limit headers yet.
+    pub fn last_rate_limit_info(&self) -> Option<RateLimitInfo> {
+        self.rate_limit_gate.last_info()
+    }
+
+    /// Set the base URL of the Spotify Web API, e.g. to use a proxy or a mock
+    /// server.
+    ///
+    /// Defaults to `https://api.spotify.com/v1`.
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.config.api_url = Some(api_url.into());
+        self
+    }
+
+    /// Set the maximum amount of times a request is retried if it fails with
+    /// a transient error, up to 10. Read more in [`ClientConfig::max_retries`](ClientConfig#structfield.max_retries).
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.config.max_retries = Some(max_retries.min(MAX_RETRIES));
+        self
+    }
+
+    /// Set the timeout of each request. By default, requests don't time out.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self.http = self.config.build_http();
+        self
+    }
+
+    /// Enable or disable compressed responses.
+    ///
+    /// When enabled, the client asks Spotify for compressed responses (which
+    /// can notably reduce the size of large responses, such as playlists) and
+    /// decompresses them automatically. The supported encodings depend on which
+    /// of the `gzip`, `brotli` and `deflate` features are enabled (none of them
+    /// are enabled by default).
+    ///
+    /// Compression is enabled by default with any of these features.
+    #[cfg(any(feature = "gzip", feature = "brotli", feature = "deflate"))]
+    pub fn with_compression(mut self, enabled: bool) -> Self {
+        self.config.compression = Some(enabled);
+        self.http = self.config.build_http();
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request to the Spotify API.
+    ///
+    /// Defaults to `spotify-rs/{version}`, e.g. `spotify-rs/0.4.0`.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Set the default market, as an [ISO 3166-1 alpha-2](https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2)
+    /// country code, used by every endpoint that accepts a market.
+    ///
+    /// A market set on an individual endpoint (e.g. with
+    /// [`AlbumEndpoint::market`](crate::endpoint::album::AlbumEndpoint::market))
+    /// takes precedence over this one. If neither is set, no market is sent,
+    /// and Spotify uses the country associated with the user access token.
+    pub fn with_market(mut self, market: impl Into<String>) -> Self {
+        self.config.market = Some(market.into());
+        self
+    }
+
+    // Get the given market, or the client's default market if there isn't one.
+    pub(crate) fn market_or_default(&self, market: Option<String>) -> Option<String> {
+        market.or_else(|| self.config.market.clone())
+    }
+
+    // Remove the API URL from a URL returned by Spotify (e.g. the next page),
+    // as it's prepended to every request.
+    pub(crate) fn relative_url(&self, url: &str) -> String {
+        let url = url.strip_prefix(API_URL).unwrap_or(url);
+        url.strip_prefix(self.config.api_url())
+            .unwrap_or(url)
+            .to_owned()
+    }
+
+    // Send a request made by the OAuth2 client (to get or refresh a token)
+    // through the transport, with the client's user agent and metrics. The
+    // rate limit gate and retries only apply to the Web API, so they're skipped.
+    async fn send_token_request(
+        &self,
+        request: oauth2::HttpRequest,
+    ) -> std::result::Result<oauth2::HttpResponse, oauth2::reqwest::Error<reqwest::Error>> {
+        let req = self
+            .http
+            .request(request.method, request.url)
+            .headers(request.headers)
+            .header(USER_AGENT, self.config.user_agent())
+            .body(request.body)
+            .build()
+            .map_err(oauth2::reqwest::Error::Reqwest)?;
+
+        info!("{} token request sent to {}", req.method(), req.url());
+
+        if let Some(metrics) = &self.metrics {
+            metrics.on_request(req.method(), req.url().path());
+        }
+
+        let sent_at = Instant::now();
+        let result = match &self.transport {
+            Some(transport) => transport.execute(req).await,
+            None => Transport::execute(&self.http, req).await,
+        };
+
+        let res = match result {
+            Ok(res) => res,
+            Err(err) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.on_error(&err);
+                }
+
+                return Err(match err {
+                    Error::Http { source } => oauth2::reqwest::Error::Reqwest(source),
+                    err => oauth2::reqwest::Error::Other(err.to_string()),
+                });
+            }
+        };
+
+        if let Some(metrics) = &self.metrics {
+            metrics.on_response(res.status(), sent_at.elapsed());
+        }
+
+        let status_code = res.status();
+        let headers = res.headers().clone();
+        let body = res.bytes().await.map_err(oauth2::reqwest::Error::Reqwest)?;
+
+        Ok(oauth2::HttpResponse {
+            status_code,
+            headers,
+            body: body.to_vec(),
+        })
+    }
+}
+
+impl<F: AuthFlow + Authorised> Client<Token, F> {
+    /// Refresh the access token, even if it hasn't expired, returning the new
+    /// token (e.g. to persist it).
+    ///
+    /// The client's token is updated as well. This method will fail if the
+    /// client doesn't have a refresh token.
+    pub async fn refresh(&self) -> Result<Token> {
+        self.exchange_refresh_token().await?;
+
+        Ok(self
+            .auth_state
+            .read()
+            .expect("The lock holding the token has been poisoned.")
+            .clone())
+    }
 }
 
 impl Client<Token, UnknownFlow> {
@@ -115,11 +513,54 @@ impl Client<Token, UnknownFlow> {
             auth_flow: UnknownFlow,
             oauth: oauth_client,
             http: reqwest::Client::new(),
+            etag_cache: None,
+            concurrency_limit: None,
+            transport: None,
+            metrics: None,
+            config: ClientConfig::default(),
+            rate_limit_gate: Arc::default(),
+            save_state_cache: None,
         })
     }
+
+    /// Create a new authenticated and authorised client from a cached refresh
+    /// token, like [`from_refresh_token`](Self::from_refresh_token), reading the
+    /// client ID, refresh token and (optional) client secret from the
+    /// `SPOTIFY_CLIENT_ID`, `SPOTIFY_REFRESH_TOKEN` and `SPOTIFY_CLIENT_SECRET`
+    /// environment variables.
+    ///
+    /// The client secret should be omitted if the refresh token was obtained
+    /// with the Authorisation Code with PKCE Flow.
+    ///
+    /// This method will fail if one of the required variables is missing,
+    /// or if the refresh token is invalid.
+    pub async fn from_env(scopes: Option<Scopes>, auto_refresh: bool) -> Result<Self> {
+        let client_id = env_var(CLIENT_ID_VAR)?;
+        let refresh_token = env_var(REFRESH_TOKEN_VAR)?;
+        let client_secret = std::env::var(CLIENT_SECRET_VAR).ok();
+
+        Self::from_refresh_token(
+            client_id,
+            client_secret.as_deref(),
+            scopes,
+            auto_refresh,
+            refresh_token,
+        )
+        .await
+    }
 }
 
 impl<F: AuthFlow> Client<Token, F> {
+    /// Send a `GET` request to an endpoint (e.g. `/tracks/{id}?market=GB`),
+    /// returning the deserialized response along with the raw JSON it was
+    /// deserialized from.
+    ///
+    /// This is meant for debugging, e.g. when a response doesn't match what
+    /// you expect, as keeping the raw JSON has a cost.
+    pub async fn get_with_raw<T: DeserializeOwned>(&self, endpoint: &str) -> Result<Raw<T>> {
+        self.get::<(), _>(self.relative_url(endpoint), None).await
+    }
+
     /// Get a reference to the client's token.
     ///
     /// Please note that the [RwLock] used here is **not** async-aware, and thus
@@ -173,13 +614,19 @@ impl<F: AuthFlow> Client<Token, F> {
             refresh_token.clone()
         };
 
-        let token = self
+        let mut token = self
             .oauth
             .exchange_refresh_token(&refresh_token)
-            .request_async(async_http_client)
+            .request_async(|req| self.send_token_request(req))
             .await?
             .set_timestamps();
 
+        // Spotify doesn't always return a new refresh token, in which case
+        // the current one can still be used.
+        if token.refresh_token.is_none() {
+            token.refresh_token = Some(refresh_token);
+        }
+
         let mut lock = self
             .auth_state
             .write()
@@ -188,6 +635,29 @@ impl<F: AuthFlow> Client<Token, F> {
         Ok(())
     }
 
+    /// Make sure the access token is valid, refreshing it if it has expired.
+    ///
+    /// This is a no-op if the token hasn't expired yet. Unlike the automatic
+    /// refresh, it will refresh the token regardless of `auto_refresh`, which
+    /// makes it useful for failing early on authentication issues before
+    /// sending several requests.
+    ///
+    /// This method will fail if the token has expired and it can't be refreshed.
+    pub async fn ensure_valid_token(&self) -> Result<()> {
+        let token_expired = self
+            .auth_state
+            .read()
+            .expect("The lock holding the token has been poisoned.")
+            .is_expired();
+
+        if token_expired {
+            info!("The token has expired, attempting to refresh...");
+            self.exchange_refresh_token().await?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) async fn request<P: Serialize + Debug, T: DeserializeOwned>(
         &self,
         method: Method,
@@ -195,13 +665,52 @@ impl<F: AuthFlow> Client<Token, F> {
         query: Option<P>,
         body: Option<Body<P>>,
     ) -> Result<T> {
-        let (token_expired, secret) = {
+        self.request_retryable(method, endpoint, query, body, false)
+            .await
+    }
+
+    // Send a request, which may be retried (if enabled) if it's a `GET`
+    // request or if `retryable` is `true`.
+    pub(crate) async fn request_retryable<P: Serialize + Debug, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<P>,
+        body: Option<Body<P>>,
+        retryable: bool,
+    ) -> Result<T> {
+        let result = self
+            .send_request(method, endpoint, query, body, retryable)
+            .await;
+
+        if let (Some(metrics), Err(err)) = (&self.metrics, &result) {
+            metrics.on_error(err);
+        }
+
+        result
+    }
+
+    async fn send_request<P: Serialize + Debug, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<P>,
+        body: Option<Body<P>>,
+        retryable: bool,
+    ) -> Result<T> {
+        let retryable = retryable || method == Method::GET;
+
+        let (token_expired, secret, cache_scope) = {
             let lock = self
                 .auth_state
                 .read()
                 .expect("The lock holding the token has been poisoned.");
 
-            (lock.is_expired(), lock.access_token.secret().to_owned())
+            (
+                lock.is_expired(),
+                lock.access_token.secret().to_owned(),
+                lock.cache_scope::<F>(self.oauth.client_id()),
+            )
         };
 
         if token_expired {
@@ -224,8 +733,9 @@ impl<F: AuthFlow> Client<Token, F> {
 
         let mut req = {
             self.http
-                .request(method, format!("{API_URL}{endpoint}"))
+                .request(method, format!("{}{endpoint}", self.config.api_url()))
                 .bearer_auth(secret)
+                .header(USER_AGENT, self.config.user_agent())
         };
 
         if let Some(q) = query {
@@ -244,48 +754,156 @@ impl<F: AuthFlow> Client<Token, F> {
             req = req.header(CONTENT_LENGTH, 0);
         }
 
-        let req = req.build()?;
-        info!(headers = ?req.headers(), "{} request sent to {}", req.method(), req.url());
+        let mut req = req.build()?;
+
+        // Only GET requests are cached, keyed by their full URL and whose
+        // token they were sent with, so responses are never shared between users.
+        let cache = self
+            .etag_cache
+            .as_ref()
+            .filter(|_| req.method() == Method::GET)
+            .map(|c| (c, format!("{cache_scope:016x} {}", req.url())));
+        let cached = cache.as_ref().and_then(|(c, key)| c.get(key));
+
+        if let Some(etag) = cached
+            .as_ref()
+            .and_then(|c| HeaderValue::from_str(&c.etag).ok())
+        {
+            req.headers_mut().insert(IF_NONE_MATCH, etag);
+        }
+
+        // Hold a permit (if the concurrency is limited) until the response
+        // has been read.
+        let _permit = match &self.concurrency_limit {
+            Some(limit) => limit.acquire().await.ok(),
+            None => None,
+        };
+
+        let res = self.execute(req, retryable).await?;
 
-        let res = self.http.execute(req).await?;
+        if res.status() == StatusCode::NOT_MODIFIED {
+            // Spotify should only respond with this to a request with an ETag,
+            // i.e. one that has a cached response.
+            let Some(cached) = cached else {
+                return Err(Error::UnexpectedResponse {
+                    description: "Got 304 Not Modified without a cached response.".to_owned(),
+                });
+            };
+
+            info!("The response hasn't been modified, using the cached response.");
+            return deserialize_response(&cached.body);
+        }
 
         if res.status().is_success() {
-            let bytes = res.bytes().await?;
+            let etag = res
+                .headers()
+                .get(ETAG)
+                .and_then(|e| e.to_str().ok())
+                .map(ToOwned::to_owned);
 
-            // Try to deserialize from bytes of JSON text;
-            let deserialized = serde_json::from_slice::<T>(&bytes).or_else(|e| {
-                // if the previous operation fails, try deserializing straight
-                // from the bytes, which works for Nil.
-                let de: BytesDeserializer<'_, serde::de::value::Error> =
-                    bytes.as_ref().into_deserializer();
+            let bytes = res.bytes().await?;
 
-                // This line also converts the serde::de::value::Error to a serde_json::Error
-                // to make it clearer to the end user that deserialization failed.
-                T::deserialize(de).map_err(|_| e)
-            });
-            // .context(DeserializationSnafu { body });
-
-            match deserialized {
-                Ok(content) => Ok(content),
-                Err(err) => {
-                    let body = std::str::from_utf8(&bytes).map_err(|_| Error::InvalidResponse)?;
-
-                    tracing::error!(
-                        %body,
-                        "Failed to deserialize the response body into an object or Nil."
-                    );
-
-                    Err(Error::Deserialization {
-                        source: err,
-                        body: body.to_owned(),
-                    })
-                }
+            if let (Some((cache, key)), Some(etag)) = (cache, etag) {
+                cache.set(
+                    &key,
+                    CachedResponse {
+                        etag,
+                        body: bytes.to_vec(),
+                    },
+                );
             }
+
+            deserialize_response(&bytes)
         } else {
             Err(res.json::<SpotifyError>().await?.into())
         }
     }
 
+    // Send a request, retrying it if it's retryable and it fails with a
+    // transient error: a `429 Too Many Requests`, a server error, or a
+    // connection error or timeout.
+    async fn execute(
+        &self,
+        mut req: reqwest::Request,
+        retryable: bool,
+    ) -> Result<reqwest::Response> {
+        let max_retries = if retryable {
+            self.config.max_retries()
+        } else {
+            0
+        };
+        let mut attempt = 0;
+
+        loop {
+            // Keep a copy of the request, in case it has to be retried.
+            let retry_req = if attempt < max_retries {
+                req.try_clone()
+            } else {
+                None
+            };
+
+            self.rate_limit_gate.wait().await;
+
+            info!(headers = ?req.headers(), "{} request sent to {}", req.method(), req.url());
+
+            if let Some(metrics) = &self.metrics {
+                metrics.on_request(req.method(), req.url().path());
+            }
+
+            let sent_at = Instant::now();
+            let result = match &self.transport {
+                Some(transport) => transport.execute(req).await,
+                None => Transport::execute(&self.http, req).await,
+            };
+
+            if let (Some(metrics), Ok(res)) = (&self.metrics, &result) {
+                metrics.on_response(res.status(), sent_at.elapsed());
+            }
+
+            if let Ok(res) = &result
+                && let Some(info) = RateLimitInfo::from_headers(res.headers())
+            {
+                self.rate_limit_gate.set_last_info(info);
+            }
+
+            if let Ok(res) = &result
+                && res.status() == StatusCode::TOO_MANY_REQUESTS
+            {
+                let retry_after = res
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|r| r.to_str().ok())
+                    .and_then(|r| r.parse().ok())
+                    .map_or(DEFAULT_RETRY_AFTER, Duration::from_secs);
+
+                info!("Rate limited, delaying requests for {retry_after:?}.");
+                self.rate_limit_gate.close_for(retry_after);
+            }
+
+            let transient = match &result {
+                Ok(res) => {
+                    res.status() == StatusCode::TOO_MANY_REQUESTS || res.status().is_server_error()
+                }
+                Err(err) => matches!(
+                    err.network_error_kind(),
+                    Some(NetworkErrorKind::Connect | NetworkErrorKind::Timeout)
+                ),
+            };
+
+            match retry_req {
+                Some(retry_req) if transient => {
+                    let delay = retry_delay(attempt);
+                    attempt += 1;
+
+                    info!("The request failed with a transient error, retrying in {delay:?} (attempt {attempt} of {max_retries}).");
+                    tokio::time::sleep(delay).await;
+                    req = retry_req;
+                }
+                _ => return result,
+            }
+        }
+    }
+
     pub(crate) async fn get<P: Serialize + Debug, T: DeserializeOwned>(
         &self,
         endpoint: String,
@@ -322,7 +940,86 @@ impl<F: AuthFlow> Client<Token, F> {
     }
 }
 
+// Deserialize a successful response body into `T`.
+fn deserialize_response<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    // An empty body (e.g. from a `204 No Content` response) is treated as
+    // `null`, so it can be deserialized as an `Option`.
+    let json: &[u8] = if bytes.is_empty() { b"null" } else { bytes };
+
+    // Try to deserialize from bytes of JSON text;
+    let deserialized = serde_json::from_slice::<T>(json).or_else(|e| {
+        // if the previous operation fails, try deserializing straight
+        // from the bytes, which works for Nil.
+        let de: BytesDeserializer<'_, serde::de::value::Error> = bytes.into_deserializer();
+
+        // This line also converts the serde::de::value::Error to a serde_json::Error
+        // to make it clearer to the end user that deserialization failed.
+        T::deserialize(de).map_err(|_| e)
+    });
+
+    match deserialized {
+        Ok(content) => Ok(content),
+        Err(err) => {
+            let body = std::str::from_utf8(bytes).map_err(|_| Error::InvalidResponse)?;
+
+            tracing::error!(
+                %body,
+                "Failed to deserialize the response body into an object or Nil."
+            );
+
+            Err(Error::Deserialization {
+                source: err,
+                body: body.to_owned(),
+            })
+        }
+    }
+}
+
+// The delay before retrying a request after the given attempt (starting at `0`),
+// which doubles with every retry, up to `MAX_RETRY_DELAY`.
+fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .checked_mul(2u32.saturating_pow(attempt))
+        .map_or(MAX_RETRY_DELAY, |delay| delay.min(MAX_RETRY_DELAY))
+}
+
+// Read an environment variable, failing with an error naming it if it's missing.
+fn env_var(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| Error::MissingEnvVar {
+        name: name.to_owned(),
+    })
+}
+
 impl AuthCodeClient<Unauthenticated> {
+    /// Create a new client and generate an authorisation URL, like [`new`](Self::new),
+    /// reading the client ID, secret and redirect URI from the `SPOTIFY_CLIENT_ID`,
+    /// `SPOTIFY_CLIENT_SECRET` and `SPOTIFY_REDIRECT_URI` environment variables.
+    ///
+    /// If a refresh token was cached, use [`Client::from_env`] instead, which
+    /// doesn't require the user to authorise the app again.
+    ///
+    /// This method will fail if one of the variables is missing, or if the
+    /// redirect URI is invalid.
+    pub fn from_env<S>(scopes: S, auto_refresh: bool) -> Result<(Self, Url)>
+    where
+        S: Into<Scopes>,
+    {
+        let client_id = env_var(CLIENT_ID_VAR)?;
+        let client_secret = env_var(CLIENT_SECRET_VAR)?;
+        let redirect_uri =
+            RedirectUrl::new(env_var(REDIRECT_URI_VAR)?).map_err(|e| Error::InvalidArgument {
+                description: format!("The {REDIRECT_URI_VAR} environment variable is invalid: {e}"),
+            })?;
+
+        Ok(Self::new(
+            client_id,
+            client_secret,
+            scopes,
+            redirect_uri,
+            auto_refresh,
+        ))
+    }
+
     /// Create a new client and generate an authorisation URL
     ///
     /// You must redirect the user to the returned URL, which in turn redirects them to
@@ -362,6 +1059,13 @@ impl AuthCodeClient<Unauthenticated> {
                 auth_flow: AuthCodeFlow { csrf_token },
                 oauth,
                 http: reqwest::Client::new(),
+                etag_cache: None,
+                concurrency_limit: None,
+                transport: None,
+                metrics: None,
+                config: ClientConfig::default(),
+                rate_limit_gate: Arc::default(),
+                save_state_cache: None,
             },
             auth_url,
         )
@@ -386,7 +1090,7 @@ impl AuthCodeClient<Unauthenticated> {
         let token = self
             .oauth
             .exchange_code(AuthorizationCode::new(auth_code))
-            .request_async(async_http_client)
+            .request_async(|req| self.send_token_request(req))
             .await?
             .set_timestamps();
 
@@ -396,6 +1100,13 @@ impl AuthCodeClient<Unauthenticated> {
             auth_flow: self.auth_flow,
             oauth: self.oauth,
             http: self.http,
+            etag_cache: self.etag_cache,
+            concurrency_limit: self.concurrency_limit,
+            transport: self.transport,
+            metrics: self.metrics,
+            config: self.config,
+            rate_limit_gate: self.rate_limit_gate,
+            save_state_cache: self.save_state_cache,
         })
     }
 }
@@ -445,6 +1156,13 @@ impl AuthCodePkceClient<Unauthenticated> {
                 },
                 oauth,
                 http: reqwest::Client::new(),
+                etag_cache: None,
+                concurrency_limit: None,
+                transport: None,
+                metrics: None,
+                config: ClientConfig::default(),
+                rate_limit_gate: Arc::default(),
+                save_state_cache: None,
             },
             auth_url,
         )
@@ -477,7 +1195,7 @@ impl AuthCodePkceClient<Unauthenticated> {
             .oauth
             .exchange_code(AuthorizationCode::new(auth_code))
             .set_pkce_verifier(pkce_verifier)
-            .request_async(async_http_client)
+            .request_async(|req| self.send_token_request(req))
             .await?
             .set_timestamps();
 
@@ -487,11 +1205,27 @@ impl AuthCodePkceClient<Unauthenticated> {
             auth_flow: self.auth_flow,
             oauth: self.oauth,
             http: self.http,
+            etag_cache: self.etag_cache,
+            concurrency_limit: self.concurrency_limit,
+            transport: self.transport,
+            metrics: self.metrics,
+            config: self.config,
+            rate_limit_gate: self.rate_limit_gate,
+            save_state_cache: self.save_state_cache,
         })
     }
 }
 
 impl ClientCredsClient<Unauthenticated> {
+    /// Authenticate the client like [`authenticate`](Self::authenticate),
+    /// reading the client ID and secret from the `SPOTIFY_CLIENT_ID` and
+    /// `SPOTIFY_CLIENT_SECRET` environment variables.
+    ///
+    /// This method will fail if one of the variables is missing.
+    pub async fn from_env() -> Result<ClientCredsClient<Token>> {
+        Self::authenticate(env_var(CLIENT_ID_VAR)?, env_var(CLIENT_SECRET_VAR)?).await
+    }
+
     /// This will exchange the client credentials for an access token used
     /// to make requests.
     ///
@@ -523,6 +1257,13 @@ impl ClientCredsClient<Unauthenticated> {
             auth_flow: ClientCredsFlow,
             oauth,
             http: reqwest::Client::new(),
+            etag_cache: None,
+            concurrency_limit: None,
+            transport: None,
+            metrics: None,
+            config: ClientConfig::default(),
+            rate_limit_gate: Arc::default(),
+            save_state_cache: None,
         })
     }
 }
@@ -576,6 +1317,13 @@ impl AuthCodeClient<Token> {
             auth_flow,
             oauth: oauth_client,
             http,
+            etag_cache: None,
+            concurrency_limit: None,
+            transport: None,
+            metrics: None,
+            config: ClientConfig::default(),
+            rate_limit_gate: Arc::default(),
+            save_state_cache: None,
         })
     }
 }
@@ -627,11 +1375,38 @@ impl AuthCodePkceClient<Token> {
             auth_flow,
             oauth: oauth_client,
             http,
+            etag_cache: None,
+            concurrency_limit: None,
+            transport: None,
+            metrics: None,
+            config: ClientConfig::default(),
+            rate_limit_gate: Arc::default(),
+            save_state_cache: None,
         })
     }
 }
 
 impl ClientCredsClient<Token> {
+    /// Request a new access token using the client credentials, even if the
+    /// current one hasn't expired, returning the new token.
+    ///
+    /// The client's token is updated as well.
+    pub async fn refresh(&self) -> Result<Token> {
+        let token = self
+            .oauth
+            .exchange_client_credentials()
+            .request_async(|req| self.send_token_request(req))
+            .await?
+            .set_timestamps();
+
+        let mut lock = self
+            .auth_state
+            .write()
+            .expect("The lock holding the token has been poisoned.");
+        *lock = token.clone();
+        Ok(token)
+    }
+
     /// Create a new authenticated client from an access token.
     /// This client will not be able to access user data.
     ///
@@ -672,6 +1447,121 @@ impl ClientCredsClient<Token> {
             auth_flow: ClientCredsFlow,
             oauth: oauth_client,
             http,
+            etag_cache: None,
+            concurrency_limit: None,
+            transport: None,
+            metrics: None,
+            config: ClientConfig::default(),
+            rate_limit_gate: Arc::default(),
+            save_state_cache: None,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::client::mock::MockTransport;
+
+    #[tokio::test]
+    async fn token_requests_go_through_transport() {
+        let transport = MockTransport::new(|_| {
+            let token = serde_json::json!({
+                "access_token": "new_access_token",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+                "scope": ""
+            });
+
+            (200, token.to_string())
+        });
+
+        let client = transport.client().with_user_agent("test-agent");
+        *client.auth_state.write().unwrap() = Token::new(
+            "access_token",
+            Some("refresh_token"),
+            Utc::now(),
+            3600,
+            None,
+        );
+
+        client.exchange_refresh_token().await.unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::POST);
+        assert_eq!(requests[0].path_and_query, "/api/token");
+        assert_eq!(requests[0].headers[USER_AGENT], "test-agent");
+
+        let body = requests[0].body.as_deref().unwrap();
+        assert!(body.contains("grant_type=refresh_token"));
+        assert!(body.contains("refresh_token=refresh_token"));
+
+        // Spotify didn't return a new refresh token, so the current one is kept.
+        let token = client.auth_state.read().unwrap();
+        assert_eq!(token.secret(), "new_access_token");
+        assert_eq!(token.refresh_secret(), Some("refresh_token"));
+    }
+
+    #[test]
+    fn cache_scope_is_kept_when_the_token_is_refreshed() {
+        let token = |access_token, refresh_token| {
+            Token::new(access_token, refresh_token, Utc::now(), 3600, None)
+        };
+
+        // Client credentials don't have a refresh token, so every new token
+        // is scoped to the client ID.
+        let old = token("old", None).cache_scope::<ClientCredsFlow>("client_id");
+        let new = token("new", None).cache_scope::<ClientCredsFlow>("client_id");
+        assert_eq!(old, new);
+
+        // Users' tokens are scoped to the refresh token, if any.
+        let old = token("old", Some("refresh")).cache_scope::<AuthCodeFlow>("client_id");
+        let new = token("new", Some("refresh")).cache_scope::<AuthCodeFlow>("client_id");
+        assert_eq!(old, new);
+
+        let old = token("old", None).cache_scope::<AuthCodeFlow>("client_id");
+        let new = token("new", None).cache_scope::<AuthCodeFlow>("client_id");
+        assert_ne!(old, new);
+    }
+
+    #[tokio::test]
+    async fn not_modified_without_a_cached_response_fails() {
+        let transport = MockTransport::new(|_| (304, String::new()));
+        let client = transport.client();
+
+        let result = client
+            .get::<(), serde_json::Value>("/me".to_owned(), None)
+            .await;
+
+        assert!(matches!(result, Err(Error::UnexpectedResponse { .. })));
+    }
+
+    #[test]
+    fn with_config_replaces_earlier_settings() {
+        let transport = MockTransport::new(|_| (204, String::new()));
+        let config = ClientConfig {
+            market: Some("GB".to_owned()),
+            max_retries: Some(3),
+            ..Default::default()
+        };
+
+        let client = transport
+            .client()
+            .with_market("US")
+            .with_user_agent("test-agent")
+            .with_config(config.clone());
+
+        assert_eq!(client.config(), &config);
+        assert_eq!(client.config().user_agent(), DEFAULT_USER_AGENT);
+
+        // The settings made afterwards override the configuration's.
+        let client = client.with_market("US").with_max_retries(1);
+
+        assert_eq!(client.config().market.as_deref(), Some("US"));
+        assert_eq!(client.config().max_retries(), 1);
+        assert_eq!(client.config().api_url(), API_URL);
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, RwLock},
+};
+
+/// A cache used to make [conditional requests](https://developer.mozilla.org/en-US/docs/Web/HTTP/Conditional_requests)
+/// using [ETags](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/ETag).
+///
+/// Responses are keyed by the full URL of the request (including the query),
+/// prefixed with a hash identifying the token used to send it (its refresh
+/// token, or the access token if there's none). When a cached response exists
+/// for a key, its ETag is sent in the `If-None-Match` header, and if Spotify
+/// replies with `304 Not Modified`, the cached body is used instead.
+///
+/// Because of the token hash, a cache can safely be shared by clients
+/// authenticated as different users (e.g. by cloning a [`MemoryETagCache`]),
+/// without one user being served another user's responses, such as those of
+/// `/me` endpoints. The hash isn't guaranteed to be stable across versions of
+/// Rust, so persisted entries may stop being used after upgrading.
+///
+/// Only `GET` requests are cached. You can use [`MemoryETagCache`] or
+/// implement this trait yourself, to use your own storage.
+pub trait ETagCache: Debug + Send + Sync {
+    /// Get the cached response for the given key, if any.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Store the response for the given key.
+    fn set(&self, key: &str, response: CachedResponse);
+}
+
+/// A response stored in an [`ETagCache`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedResponse {
+    /// The ETag of the response, as returned by Spotify.
+    pub etag: String,
+    /// The raw body of the response.
+    pub body: Vec<u8>,
+}
+
+/// A simple in-memory [`ETagCache`] that never evicts any entries.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryETagCache {
+    responses: Arc<RwLock<HashMap<String, CachedResponse>>>,
+}
+
+impl MemoryETagCache {
+    /// Create a new, empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove all the cached responses.
+    pub fn clear(&self) {
+        self.responses
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+impl ETagCache for MemoryETagCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.responses
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .cloned()
+    }
+
+    fn set(&self, key: &str, response: CachedResponse) {
+        self.responses
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_owned(), response);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::IF_NONE_MATCH;
+
+    use super::*;
+    use crate::{client::mock::MockTransport, model::user::PrivateUser};
+
+    #[tokio::test]
+    async fn shared_cache_is_not_shared_between_users() {
+        let transport = MockTransport::new(|req| {
+            if req.headers.contains_key(IF_NONE_MATCH) {
+                return (304, String::new());
+            }
+
+            let token = req.headers["authorization"].to_str().unwrap();
+            let id = token.trim_start_matches("Bearer ");
+            let user = serde_json::json!({
+                "display_name": id,
+                "external_urls": { "spotify": format!("https://open.spotify.com/user/{id}") },
+                "followers": { "href": null, "total": 0 },
+                "href": format!("https://api.spotify.com/v1/users/{id}"),
+                "id": id,
+                "images": [],
+                "type": "user",
+                "uri": format!("spotify:user:{id}")
+            });
+
+            (200, user.to_string())
+        });
+
+        let cache = MemoryETagCache::new();
+        let alice = transport
+            .client_with_token("alice")
+            .with_etag_cache(cache.clone());
+        let bob = transport.client_with_token("bob").with_etag_cache(cache);
+
+        let user: PrivateUser = alice.get::<(), _>("/me".to_owned(), None).await.unwrap();
+        assert_eq!(user.id, "alice");
+
+        let user: PrivateUser = bob.get::<(), _>("/me".to_owned(), None).await.unwrap();
+        assert_eq!(user.id, "bob");
+
+        // Requesting it again as the first user uses the cached response.
+        let user: PrivateUser = alice.get::<(), _>("/me".to_owned(), None).await.unwrap();
+        assert_eq!(user.id, "alice");
+
+        let requests = transport.requests();
+        assert!(!requests[0].headers.contains_key(IF_NONE_MATCH));
+        assert!(!requests[1].headers.contains_key(IF_NONE_MATCH));
+        assert!(requests[2].headers.contains_key(IF_NONE_MATCH));
+    }
+}
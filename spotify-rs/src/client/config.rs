@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer};
+
+use super::{API_URL, DEFAULT_USER_AGENT, MAX_RETRIES};
+
+/// The configuration of a [`Client`](super::Client).
+///
+/// Every field is optional, with `None` meaning the default is used. The
+/// configuration can be deserialized (e.g. from a configuration file) and
+/// applied to a client in one step using
+/// [`with_config`](super::Client::with_config), while each of the `with_*`
+/// methods of the client sets a single field.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ClientConfig {
+    /// The base URL of the Spotify Web API, e.g. to use a proxy or a mock server.
+    ///
+    /// Defaults to `https://api.spotify.com/v1`.
+    pub api_url: Option<String>,
+    /// The `User-Agent` header sent with every request.
+    ///
+    /// Defaults to `spotify-rs/{version}`, e.g. `spotify-rs/0.4.0`.
+    pub user_agent: Option<String>,
+    /// The default market, used by every endpoint that accepts a market
+    /// when one isn't set explicitly.
+    pub market: Option<String>,
+    /// The timeout of each request. When deserialized, it's a number of seconds.
+    ///
+    /// By default, requests don't time out.
+    #[serde(deserialize_with = "deserialize_secs")]
+    pub timeout: Option<Duration>,
+    /// The maximum amount of times a request is retried if it fails with a
+    /// transient error: a `429 Too Many Requests`, a server error (`5xx`), or
+    /// a connection error or timeout. Retries are delayed exponentially,
+    /// starting at 500 milliseconds, up to 30 seconds.
+    ///
+    /// Only requests that are safe to repeat are retried: all `GET` requests,
+    /// and the requests explicitly marked as retryable, such as
+    /// [setting the volume](crate::endpoint::player::SetPlaybackVolumeEndpoint::retryable).
+    /// Other requests (e.g. adding an item to the queue) are never retried,
+    /// as that could apply them twice.
+    ///
+    /// Defaults to `0` (no retries), and is capped at `10`.
+    pub max_retries: Option<u32>,
+    /// Whether or not compressed responses are requested. This requires any
+    /// of the `gzip`, `brotli` or `deflate` features, and is ignored otherwise.
+    ///
+    /// Defaults to `true`.
+    pub compression: Option<bool>,
+}
+
+impl ClientConfig {
+    pub(crate) fn api_url(&self) -> &str {
+        self.api_url.as_deref().unwrap_or(API_URL)
+    }
+
+    pub(crate) fn user_agent(&self) -> &str {
+        self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT)
+    }
+
+    pub(crate) fn max_retries(&self) -> u32 {
+        self.max_retries.unwrap_or(0).min(MAX_RETRIES)
+    }
+
+    // Build an HTTP client using the settings that apply to the HTTP client
+    // itself (rather than to individual requests).
+    pub(crate) fn build_http(&self) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        #[cfg(any(feature = "gzip", feature = "brotli", feature = "deflate"))]
+        {
+            let enabled = self.compression.unwrap_or(true);
+
+            #[cfg(feature = "gzip")]
+            {
+                builder = builder.gzip(enabled);
+            }
+            #[cfg(feature = "brotli")]
+            {
+                builder = builder.brotli(enabled);
+            }
+            #[cfg(feature = "deflate")]
+            {
+                builder = builder.deflate(enabled);
+            }
+        }
+
+        builder
+            .build()
+            .expect("the HTTP client should be built with a valid configuration")
+    }
+}
+
+// Deserialize an optional duration from a number of seconds.
+fn deserialize_secs<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let secs = Option::<f64>::deserialize(deserializer)?;
+
+    secs.map(|s| Duration::try_from_secs_f64(s).map_err(serde::de::Error::custom))
+        .transpose()
+}
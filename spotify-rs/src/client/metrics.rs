@@ -0,0 +1,38 @@
+use std::{fmt::Debug, time::Duration};
+
+use reqwest::{Method, StatusCode};
+
+use crate::error::Error;
+
+/// Receives events about the requests sent by a [`Client`](super::Client),
+/// e.g. to count requests and errors or to record latencies, using any
+/// metrics library.
+///
+/// It can be set using [`with_metrics`](super::Client::with_metrics). Every
+/// method does nothing by default, so only the needed ones have to be
+/// implemented. They're called while sending requests, so they should be quick.
+pub trait MetricsRecorder: Debug + Send + Sync {
+    /// Called before a request is sent (including every retry), with the path
+    /// of its URL, e.g. `/v1/tracks/4cOdK2wGLETKBW3PvgPWqT`, or `/api/token`
+    /// for the requests that get or refresh a token.
+    ///
+    /// The path is the actual one, including any IDs, rather than a template,
+    /// so it can have a very high cardinality. To use it as a label (e.g. of a
+    /// Prometheus metric), it's best to reduce it first, such as to the first
+    /// segment after the version (e.g. `tracks`).
+    fn on_request(&self, method: &Method, path: &str) {
+        let _ = (method, path);
+    }
+
+    /// Called when a response is received (including error responses), with
+    /// the time elapsed since the request was sent.
+    fn on_response(&self, status: StatusCode, elapsed: Duration) {
+        let _ = (status, elapsed);
+    }
+
+    /// Called once for every request that failed, after any retries, whether
+    /// the error was returned by Spotify or not (e.g. a timeout).
+    fn on_error(&self, error: &Error) {
+        let _ = error;
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use chrono::Utc;
+use oauth2::{AuthUrl, ClientId, CsrfToken, TokenUrl};
+use reqwest::{header::HeaderMap, Method};
+
+use crate::auth::{AuthCodeFlow, Token};
+
+use super::{
+    AuthCodeClient, ClientConfig, OAuthClient, Transport, TransportFuture, AUTHORISATION_URL,
+    TOKEN_URL,
+};
+
+type Handler = dyn Fn(&RecordedRequest) -> (u16, String) + Send + Sync;
+
+/// A request sent through a [`MockTransport`].
+#[derive(Clone, Debug)]
+pub(crate) struct RecordedRequest {
+    pub(crate) method: Method,
+    /// The path and query of the URL, e.g. `/v1/episodes?ids=a,b`.
+    pub(crate) path_and_query: String,
+    pub(crate) body: Option<String>,
+    pub(crate) headers: HeaderMap,
+}
+
+impl RecordedRequest {
+    // Get the value of a query parameter, if present.
+    pub(crate) fn query(&self, key: &str) -> Option<String> {
+        let url = reqwest::Url::parse(&format!("http://localhost{}", self.path_and_query)).ok()?;
+
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    }
+}
+
+/// A transport that answers every request using a handler, returning its
+/// status code and JSON body (with the ETag `"mock"`), and records the
+/// requests it receives.
+#[derive(Clone)]
+pub(crate) struct MockTransport {
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockTransport {
+    pub(crate) fn new(
+        handler: impl Fn(&RecordedRequest) -> (u16, String) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            requests: Arc::default(),
+        }
+    }
+
+    /// The requests received so far, in the order they were sent.
+    pub(crate) fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Create a client with a valid token, which sends every request through
+    /// this transport.
+    pub(crate) fn client(&self) -> AuthCodeClient<Token> {
+        self.client_with_token("access_token")
+    }
+
+    /// Create a client like [`client`](Self::client), using the given access
+    /// token.
+    pub(crate) fn client_with_token(&self, access_token: &str) -> AuthCodeClient<Token> {
+        let oauth = OAuthClient::new(
+            ClientId::new("client_id".to_owned()),
+            None,
+            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
+            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
+        );
+
+        AuthCodeClient {
+            auto_refresh: false,
+            auth_state: Arc::new(RwLock::new(Token::new(
+                access_token,
+                None,
+                Utc::now(),
+                3600,
+                None,
+            ))),
+            auth_flow: AuthCodeFlow {
+                csrf_token: CsrfToken::new("state".to_owned()),
+            },
+            oauth,
+            http: reqwest::Client::new(),
+            etag_cache: None,
+            concurrency_limit: None,
+            transport: Some(Arc::new(self.clone())),
+            metrics: None,
+            config: ClientConfig::default(),
+            rate_limit_gate: Arc::default(),
+            save_state_cache: None,
+        }
+    }
+}
+
+impl Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockTransport").finish_non_exhaustive()
+    }
+}
+
+impl Transport for MockTransport {
+    fn execute(&self, request: reqwest::Request) -> TransportFuture<'_> {
+        let url = request.url();
+        let path_and_query = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_owned(),
+        };
+
+        let recorded = RecordedRequest {
+            method: request.method().clone(),
+            path_and_query,
+            body: request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(|b| String::from_utf8_lossy(b).into_owned()),
+            headers: request.headers().clone(),
+        };
+
+        let (status, body) = (self.handler)(&recorded);
+        self.requests.lock().unwrap().push(recorded);
+
+        let response = http::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .header("etag", "\"mock\"")
+            .body(body)
+            .unwrap();
+
+        Box::pin(async move { Ok(response.into()) })
+    }
+}
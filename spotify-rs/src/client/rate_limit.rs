@@ -0,0 +1,83 @@
+use std::{sync::Mutex, time::Duration};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use tokio::time::Instant;
+
+/// The rate limit information sent by Spotify in the headers of a response,
+/// which can be used to slow down before being rate limited.
+///
+/// Spotify doesn't always send these headers, so every field is optional.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// How long to wait before making another request (the `Retry-After` header).
+    pub retry_after: Option<Duration>,
+    /// The amount of requests that can be made in the current window
+    /// (the `X-RateLimit-Limit` header).
+    pub limit: Option<u32>,
+    /// The amount of requests left in the current window
+    /// (the `X-RateLimit-Remaining` header).
+    pub remaining: Option<u32>,
+}
+
+impl RateLimitInfo {
+    // Get the rate limit information from a response's headers, if there's any.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+
+        let info = Self {
+            retry_after: number(RETRY_AFTER.as_str()).map(Duration::from_secs),
+            limit: number("x-ratelimit-limit").and_then(|l| l.try_into().ok()),
+            remaining: number("x-ratelimit-remaining").and_then(|r| r.try_into().ok()),
+        };
+
+        (info != Self::default()).then_some(info)
+    }
+}
+
+// A gate that's closed when Spotify responds with `429 Too Many Requests`,
+// making every request wait until the `Retry-After` deadline passes.
+//
+// It's shared between the clones of a client, so that a rate limit hit by one
+// request delays all the others, instead of them making it worse.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimitGate {
+    deadline: Mutex<Option<Instant>>,
+    last_info: Mutex<Option<RateLimitInfo>>,
+}
+
+impl RateLimitGate {
+    // Wait until the gate is open.
+    pub(crate) async fn wait(&self) {
+        // The deadline may be pushed back while waiting, so it's checked again
+        // after every wait.
+        while let Some(deadline) = self.deadline().filter(|d| *d > Instant::now()) {
+            tokio::time::sleep_until(deadline).await;
+        }
+    }
+
+    // Close the gate for the given duration, unless it's already closed for longer.
+    pub(crate) fn close_for(&self, duration: Duration) {
+        let deadline = Instant::now() + duration;
+        let mut lock = self.deadline.lock().unwrap_or_else(|e| e.into_inner());
+
+        if lock.is_none_or(|d| d < deadline) {
+            *lock = Some(deadline);
+        }
+    }
+
+    // Store the rate limit information of the latest response that had any.
+    pub(crate) fn set_last_info(&self, info: RateLimitInfo) {
+        *self.last_info.lock().unwrap_or_else(|e| e.into_inner()) = Some(info);
+    }
+
+    pub(crate) fn last_info(&self) -> Option<RateLimitInfo> {
+        self.last_info
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        *self.deadline.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
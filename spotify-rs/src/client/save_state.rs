@@ -0,0 +1,48 @@
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+// An in-memory cache of whether or not tracks are saved in the current user's
+// library, used to avoid checking the same tracks repeatedly.
+//
+// Entries expire after the TTL, and are updated whenever tracks are saved or
+// removed through the client, so the cache reflects those changes immediately.
+#[derive(Debug)]
+pub(crate) struct SaveStateCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, (bool, Instant)>>,
+}
+
+impl SaveStateCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::default(),
+        }
+    }
+
+    // Get the save state of the given track, if it's cached and hasn't expired.
+    pub(crate) fn get(&self, id: &str) -> Option<bool> {
+        self.entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(id)
+            .filter(|(_, cached_at)| cached_at.elapsed() < self.ttl)
+            .map(|(saved, _)| *saved)
+    }
+
+    // Set the save state of the given tracks.
+    pub(crate) fn set<'a>(&self, states: impl IntoIterator<Item = (&'a str, bool)>) {
+        let now = Instant::now();
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+
+        // Remove the expired entries, so they don't pile up.
+        entries.retain(|_, (_, cached_at)| cached_at.elapsed() < self.ttl);
+
+        for (id, saved) in states {
+            entries.insert(id.to_owned(), (saved, now));
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::{fmt::Debug, future::Future, pin::Pin};
+
+use crate::error::Result;
+
+/// A boxed future returned by [`Transport::execute`].
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<reqwest::Response>> + Send + 'a>>;
+
+/// The transport used by a [`Client`](super::Client) to send requests to the
+/// Spotify API, which is a [`reqwest::Client`] by default.
+///
+/// It can be replaced using [`with_transport`](super::Client::with_transport),
+/// e.g. to return canned responses in tests, without hitting Spotify. Responses
+/// can be built from an `http::Response`, as [`reqwest::Response`] implements
+/// `From<http::Response<T>>`.
+///
+/// Requests to get or refresh a token go through the transport as well, except
+/// for the ones sent while creating a client, as no transport can be set yet:
+/// [`ClientCredsClient::authenticate`](super::ClientCredsClient::authenticate),
+/// [`Client::from_refresh_token`](super::Client::from_refresh_token) and the
+/// request validating the token in the `from_access_token` constructors.
+pub trait Transport: Debug + Send + Sync {
+    /// Send a request and return its response.
+    fn execute(&self, request: reqwest::Request) -> TransportFuture<'_>;
+}
+
+impl Transport for reqwest::Client {
+    fn execute(&self, request: reqwest::Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(reqwest::Client::execute(self, request).await?) })
+    }
+}
@@ -1,5 +1,32 @@
+use std::collections::BTreeMap;
+
+use oauth2::url::form_urlencoded;
 use serde::Serialize;
 
+use crate::error::{Error, Result};
+
+// Implement the `extra_param` setter for endpoints with an `extra_params` field.
+// The generic parameters of a generic endpoint are given in brackets, e.g.
+// `impl_extra_params!([S: SeedType] RecommendationsEndpoint<S>)`.
+macro_rules! impl_extra_params {
+    ([$($generics:tt)*] $t:ty) => {
+        impl<$($generics)*> $t {
+            /// Add an extra query parameter to the request, which isn't
+            /// otherwise supported (e.g. one recently added by Spotify).
+            ///
+            /// Extra parameters are added to the query after the ones set
+            /// by the other methods.
+            pub fn extra_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+                self.extra_params.insert(key.into(), value.into());
+                self
+            }
+        }
+    };
+    ($($t:ty),+ $(,)?) => {
+        $(impl_extra_params!([] $t);)+
+    };
+}
+
 pub mod album;
 pub mod artist;
 pub mod audiobook;
@@ -13,6 +40,49 @@ pub mod show;
 pub mod track;
 pub mod user;
 
+// The maximum amount of IDs most endpoints accept in a single request.
+pub(crate) const MAX_IDS: usize = 50;
+// The maximum limit most paginated endpoints accept. Limits are clamped to
+// it (rather than sent as is), as Spotify rejects the request otherwise.
+pub(crate) const MAX_LIMIT: u32 = 50;
+
+// Make sure the response to a request for a chunk of IDs has a result (or
+// `null`) for every ID, as the results can't be matched to the IDs otherwise.
+pub(crate) fn check_chunk_len<T>(
+    items: Vec<Option<T>>,
+    len: usize,
+    kind: &str,
+) -> Result<Vec<Option<T>>> {
+    if items.len() != len {
+        return Err(Error::UnexpectedResponse {
+            description: format!(
+                "Requested {len} {kind}, but Spotify returned {} results.",
+                items.len()
+            ),
+        });
+    }
+
+    Ok(items)
+}
+
+// Extra query parameters, added to the query of an endpoint after its own.
+pub(crate) type ExtraParams = BTreeMap<String, String>;
+
+// Add the extra query parameters to the URL of an endpoint that's sent as the
+// body of the request, as they can't be serialized along with it.
+pub(crate) fn with_extra_params(endpoint: String, extra_params: &ExtraParams) -> String {
+    if extra_params.is_empty() {
+        return endpoint;
+    }
+
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(extra_params)
+        .finish();
+    let separator = if endpoint.contains('?') { '&' } else { '?' };
+
+    format!("{endpoint}{separator}{query}")
+}
+
 // Authenticated client type to make it more convenient to use in the endpoints.
 type Client<F> = crate::client::Client<crate::auth::Token, F>;
 
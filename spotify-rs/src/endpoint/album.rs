@@ -12,7 +12,10 @@ use crate::{
     query_list, Nil,
 };
 
-use super::{Client, Endpoint};
+use super::{check_chunk_len, Client, Endpoint, ExtraParams, MAX_LIMIT};
+
+// The maximum amount of album IDs that can be requested at once.
+const MAX_ALBUM_IDS: usize = 20;
 
 impl Endpoint for AlbumEndpoint {}
 impl Endpoint for AlbumsEndpoint {}
@@ -20,10 +23,19 @@ impl Endpoint for AlbumTracksEndpoint {}
 impl Endpoint for SavedAlbumsEndpoint {}
 impl Endpoint for NewReleasesEndpoint {}
 
+impl_extra_params!(
+    AlbumEndpoint,
+    AlbumsEndpoint,
+    AlbumTracksEndpoint,
+    SavedAlbumsEndpoint,
+    NewReleasesEndpoint
+);
+
 pub fn album(id: impl Into<String>) -> AlbumEndpoint {
     AlbumEndpoint {
         id: id.into(),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -31,6 +43,7 @@ pub fn albums<T: AsRef<str>>(ids: &[T]) -> AlbumsEndpoint {
     AlbumsEndpoint {
         ids: query_list(ids),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -45,31 +58,62 @@ pub fn saved_albums() -> SavedAlbumsEndpoint {
     SavedAlbumsEndpoint::default()
 }
 
+/// Save one or more albums to the current user's library.
+///
+/// Any number of IDs can be used, as they are sent in chunks of 20
+/// (the maximum Spotify allows per request).
 pub async fn save_albums<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<Nil> {
-    spotify
-        .put("/me/albums".to_owned(), body_list("ids", ids))
-        .await
+    for chunk in ids.chunks(MAX_ALBUM_IDS) {
+        spotify
+            .put::<_, Nil>("/me/albums".to_owned(), body_list("ids", chunk))
+            .await?;
+    }
+
+    Ok(Nil)
 }
 
+/// Remove one or more albums from the current user's library.
+///
+/// Any number of IDs can be used, as they are sent in chunks of 20
+/// (the maximum Spotify allows per request).
 pub async fn remove_saved_albums<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<Nil> {
-    spotify
-        .delete("/me/albums".to_owned(), body_list("ids", ids))
-        .await
+    for chunk in ids.chunks(MAX_ALBUM_IDS) {
+        spotify
+            .delete::<_, Nil>("/me/albums".to_owned(), body_list("ids", chunk))
+            .await?;
+    }
+
+    Ok(Nil)
 }
 
+/// Check if one or more albums are saved in the current user's library,
+/// returning the results in the same order as the IDs.
+///
+/// Any number of IDs can be used, as they are checked in chunks of 20
+/// (the maximum Spotify allows per request).
 pub async fn check_saved_albums<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<Vec<bool>> {
-    spotify
-        .get("/me/albums/contains".to_owned(), [("ids", query_list(ids))])
-        .await
+    let mut saved = Vec::with_capacity(ids.len());
+
+    for chunk in ids.chunks(MAX_ALBUM_IDS) {
+        let chunk_saved: Vec<bool> = spotify
+            .get(
+                "/me/albums/contains".to_owned(),
+                [("ids", query_list(chunk))],
+            )
+            .await?;
+        saved.extend(chunk_saved);
+    }
+
+    Ok(saved)
 }
 
 pub fn new_releases() -> NewReleasesEndpoint {
@@ -82,6 +126,8 @@ pub struct AlbumEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AlbumEndpoint {
@@ -92,7 +138,9 @@ impl AlbumEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Album> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Album> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get(format!("/albums/{}", self.id), self).await
     }
 }
@@ -101,6 +149,8 @@ impl AlbumEndpoint {
 pub struct AlbumsEndpoint {
     pub(crate) ids: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AlbumsEndpoint {
@@ -110,12 +160,33 @@ impl AlbumsEndpoint {
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Album>> {
-        spotify
-            .get("/albums".to_owned(), self)
-            .await
-            .map(|a: Albums| a.albums)
+    /// Send the request, returning the albums in the same order as the IDs,
+    /// with `None` for the albums that couldn't be found.
+    ///
+    /// Any number of IDs can be used, as they are requested in chunks of 20
+    /// (the maximum Spotify allows per request).
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Album>>> {
+        // Splitting no IDs would still give an (empty) ID to request.
+        if self.ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.market = spotify.market_or_default(self.market);
+        let ids: Vec<&str> = self.ids.split(',').collect();
+        let mut albums = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(MAX_ALBUM_IDS) {
+            let endpoint = Self {
+                ids: chunk.join(","),
+                market: self.market.clone(),
+                extra_params: self.extra_params.clone(),
+            };
+
+            let chunk_albums: Albums = spotify.get("/albums".to_owned(), endpoint).await?;
+            albums.extend(check_chunk_len(chunk_albums.albums, chunk.len(), "albums")?);
+        }
+
+        Ok(albums)
     }
 }
 
@@ -126,6 +197,8 @@ pub struct AlbumTracksEndpoint {
     pub(crate) market: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AlbumTracksEndpoint {
@@ -137,7 +210,7 @@ impl AlbumTracksEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -148,7 +221,9 @@ impl AlbumTracksEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedTrack>> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedTrack>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify
             .get(format!("/albums/{}/tracks", self.id), self)
             .await
@@ -160,6 +235,8 @@ pub struct SavedAlbumsEndpoint {
     pub(crate) market: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SavedAlbumsEndpoint {
@@ -171,7 +248,7 @@ impl SavedAlbumsEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -183,9 +260,11 @@ impl SavedAlbumsEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(
-        self,
+        mut self,
         spotify: &Client<impl AuthFlow + Authorised>,
     ) -> Result<Page<SavedAlbum>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get("/me/albums".to_owned(), self).await
     }
 }
@@ -195,6 +274,8 @@ pub struct NewReleasesEndpoint {
     pub(crate) country: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl NewReleasesEndpoint {
@@ -206,7 +287,7 @@ impl NewReleasesEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -224,3 +305,20 @@ impl NewReleasesEndpoint {
             .map(|p: PagedAlbums| p.albums)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockTransport;
+
+    #[tokio::test]
+    async fn albums_without_ids_sends_no_request() {
+        let transport = MockTransport::new(|_| (400, String::new()));
+        let spotify = transport.client();
+
+        let albums = albums::<&str>(&[]).get(&spotify).await.unwrap();
+
+        assert!(albums.is_empty());
+        assert!(transport.requests().is_empty());
+    }
+}
@@ -12,25 +12,42 @@ use crate::{
     query_list,
 };
 
-use super::{Client, Endpoint};
+use super::{check_chunk_len, Client, Endpoint, ExtraParams, MAX_IDS, MAX_LIMIT};
 
 impl Endpoint for ArtistAlbumsEndpoint {}
 impl Endpoint for ArtistTopTracksEndpoint {}
 
+impl_extra_params!(ArtistAlbumsEndpoint, ArtistTopTracksEndpoint);
+
 pub async fn get_artist(id: impl Into<String>, spotify: &Client<impl AuthFlow>) -> Result<Artist> {
     spotify
         .get::<(), _>(format!("/artists/{}", id.into()), None)
         .await
 }
 
+/// Get several artists, in the same order as the IDs, with `None` for the
+/// artists that couldn't be found.
+///
+/// Any number of IDs can be used, as they are requested in chunks of 50
+/// (the maximum Spotify allows per request).
 pub async fn get_artists<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow>,
-) -> Result<Vec<Artist>> {
-    spotify
-        .get("/artists".to_owned(), [("ids", query_list(ids))])
-        .await
-        .map(|a: Artists| a.artists)
+) -> Result<Vec<Option<Artist>>> {
+    let mut artists = Vec::with_capacity(ids.len());
+
+    for chunk in ids.chunks(MAX_IDS) {
+        let chunk_artists: Artists = spotify
+            .get("/artists".to_owned(), [("ids", query_list(chunk))])
+            .await?;
+        artists.extend(check_chunk_len(
+            chunk_artists.artists,
+            chunk.len(),
+            "artists",
+        )?);
+    }
+
+    Ok(artists)
 }
 
 pub fn artist_albums(id: impl Into<String>) -> ArtistAlbumsEndpoint {
@@ -40,6 +57,13 @@ pub fn artist_albums(id: impl Into<String>) -> ArtistAlbumsEndpoint {
     }
 }
 
+/// Get an artist's top tracks by country.
+///
+/// If a market isn't set, either on the endpoint or with
+/// [`Client::with_market`](crate::client::Client::with_market), the market
+/// of the current user (the country associated with the access token) is used.
+/// As there's no user when using the Client Credentials flow, a market must be
+/// set in that case, otherwise Spotify responds with an error.
 pub fn artist_top_tracks(id: impl Into<String>) -> ArtistTopTracksEndpoint {
     ArtistTopTracksEndpoint {
         id: id.into(),
@@ -55,7 +79,7 @@ pub async fn get_related_artists(id: &str, spotify: &Client<impl AuthFlow>) -> R
     spotify
         .get::<(), _>(format!("/artists/{}/related-artists", id), None)
         .await
-        .map(|a: Artists| a.artists)
+        .map(|a: Artists| a.artists.into_iter().flatten().collect())
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -66,6 +90,8 @@ pub struct ArtistAlbumsEndpoint {
     pub(crate) market: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ArtistAlbumsEndpoint {
@@ -83,7 +109,7 @@ impl ArtistAlbumsEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -94,7 +120,9 @@ impl ArtistAlbumsEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedAlbum>> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedAlbum>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify
             .get(format!("/artists/{}/albums", self.id), self)
             .await
@@ -106,6 +134,8 @@ pub struct ArtistTopTracksEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ArtistTopTracksEndpoint {
@@ -116,10 +146,16 @@ impl ArtistTopTracksEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Track>> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Track>> {
+        // Spotify requires a market for this endpoint, so `from_token` is
+        // used to default to the user's market.
+        self.market = spotify
+            .market_or_default(self.market)
+            .or_else(|| Some("from_token".to_owned()));
+
         spotify
             .get(format!("/artists/{}/top-tracks", self.id), self)
             .await
-            .map(|t: Tracks| t.tracks)
+            .map(|t: Tracks| t.tracks.into_iter().flatten().collect())
     }
 }
@@ -12,7 +12,12 @@ use crate::{
     query_list, Nil,
 };
 
-use super::{Client, Endpoint};
+use super::{Client, Endpoint, ExtraParams, MAX_LIMIT};
+
+// The markets Spotify makes audiobooks available in, as listed in the
+// Web API reference in 2024. It's a snapshot, which isn't updated
+// automatically when audiobooks launch in new markets.
+const AUDIOBOOK_MARKETS: [&str; 6] = ["US", "GB", "CA", "IE", "NZ", "AU"];
 
 impl Endpoint for AudiobookEndpoint {}
 impl Endpoint for AudiobooksEndpoint {}
@@ -21,11 +26,39 @@ impl Endpoint for SavedAudiobooksEndpoint {}
 impl Endpoint for ChapterEndpoint {}
 impl Endpoint for ChaptersEndpoint {}
 
+impl_extra_params!(
+    AudiobookEndpoint,
+    AudiobooksEndpoint,
+    AudiobookChaptersEndpoint,
+    SavedAudiobooksEndpoint,
+    ChapterEndpoint,
+    ChaptersEndpoint
+);
+
+/// Check whether audiobooks are available in a market (an ISO 3166-1
+/// alpha-2 country code, e.g. `GB`).
+///
+/// Spotify only offers audiobooks in a few markets, so this can be used to
+/// hide audiobook features for users elsewhere, instead of running into errors
+/// or empty results from the audiobook endpoints.
+///
+/// This is a best-effort check, based on a snapshot of the markets documented
+/// by Spotify in 2024 (the US, the UK, Canada, Ireland, New Zealand and
+/// Australia), so it will return `false` for markets where audiobooks have
+/// launched since. Only use it as a hint, e.g. to decide whether to show
+/// audiobook features by default.
+pub fn audiobooks_available(market: &str) -> bool {
+    AUDIOBOOK_MARKETS
+        .iter()
+        .any(|m| m.eq_ignore_ascii_case(market))
+}
+
 #[doc = include_str!("../docs/client_creds_error.md")]
 pub fn audiobook(id: impl Into<String>) -> AudiobookEndpoint {
     AudiobookEndpoint {
         id: id.into(),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -34,6 +67,7 @@ pub fn audiobooks<T: AsRef<str>>(ids: &[T]) -> AudiobooksEndpoint {
     AudiobooksEndpoint {
         ids: query_list(ids),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -84,6 +118,7 @@ pub fn chapter(id: impl Into<String>) -> ChapterEndpoint {
     ChapterEndpoint {
         id: id.into(),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -92,6 +127,7 @@ pub fn chapters<T: AsRef<str>>(ids: &[T]) -> ChaptersEndpoint {
     ChaptersEndpoint {
         ids: query_list(ids),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -100,6 +136,8 @@ pub struct AudiobookEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AudiobookEndpoint {
@@ -110,7 +148,9 @@ impl AudiobookEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Audiobook> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Audiobook> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get(format!("/audiobooks/{}", self.id), self).await
     }
 }
@@ -119,6 +159,8 @@ impl AudiobookEndpoint {
 pub struct AudiobooksEndpoint {
     pub(crate) ids: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AudiobooksEndpoint {
@@ -129,7 +171,9 @@ impl AudiobooksEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Audiobook>>> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Audiobook>>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify
             .get("/audiobooks".to_owned(), self)
             .await
@@ -144,6 +188,8 @@ pub struct AudiobookChaptersEndpoint {
     pub(crate) market: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AudiobookChaptersEndpoint {
@@ -155,7 +201,7 @@ impl AudiobookChaptersEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -166,7 +212,9 @@ impl AudiobookChaptersEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedChapter>> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedChapter>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify
             .get(format!("/audiobooks/{}/chapters", self.id), self)
             .await
@@ -177,12 +225,14 @@ impl AudiobookChaptersEndpoint {
 pub struct SavedAudiobooksEndpoint {
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SavedAudiobooksEndpoint {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -218,6 +268,8 @@ pub struct ChapterEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ChapterEndpoint {
@@ -228,7 +280,9 @@ impl ChapterEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Chapter> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Chapter> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get(format!("/chapters/{}", self.id), self).await
     }
 }
@@ -237,6 +291,8 @@ impl ChapterEndpoint {
 pub struct ChaptersEndpoint {
     pub(crate) ids: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ChaptersEndpoint {
@@ -247,7 +303,9 @@ impl ChaptersEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Chapter>>> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Chapter>>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify
             .get("/chapters/".to_owned(), self)
             .await
@@ -9,11 +9,13 @@ use crate::{
     },
 };
 
-use super::{Client, Endpoint};
+use super::{Client, Endpoint, ExtraParams, MAX_LIMIT};
 
 impl Endpoint for BrowseCategoryEndpoint {}
 impl Endpoint for BrowseCategoriesEndpoint {}
 
+impl_extra_params!(BrowseCategoryEndpoint, BrowseCategoriesEndpoint);
+
 pub fn browse_category(id: impl Into<String>) -> BrowseCategoryEndpoint {
     BrowseCategoryEndpoint {
         id: id.into(),
@@ -31,6 +33,8 @@ pub struct BrowseCategoryEndpoint {
     pub(crate) id: String,
     pub(crate) country: Option<String>,
     pub(crate) locale: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl BrowseCategoryEndpoint {
@@ -60,6 +64,8 @@ pub struct BrowseCategoriesEndpoint {
     pub(crate) locale: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl BrowseCategoriesEndpoint {
@@ -77,7 +83,7 @@ impl BrowseCategoriesEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
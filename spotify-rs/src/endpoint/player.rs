@@ -1,5 +1,6 @@
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData, time::Duration};
 
+use chrono::{DateTime, Utc};
 use reqwest::Method;
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -7,15 +8,20 @@ use serde_json::{json, Value};
 use crate::{
     auth::{AuthFlow, Authorised},
     client::Body,
-    error::Result,
+    error::{Error, Result},
     model::{
-        player::{CurrentlyPlayingItem, Device, Devices, PlayHistory, PlaybackState, Queue},
-        CursorPage,
+        player::{
+            CurrentlyPlayingItem, Device, Devices, PlayHistory, PlaybackState, Queue, Volume,
+        },
+        CursorPage, PlayableItem,
     },
     Nil,
 };
 
-use super::{Client, Endpoint};
+use super::{with_extra_params, Client, Endpoint, ExtraParams, MAX_LIMIT};
+
+// The delay between requests when adding multiple items to the queue.
+const QUEUE_INTERVAL: Duration = Duration::from_millis(100);
 
 impl Endpoint for TransferPlaybackEndpoint {}
 impl Endpoint for StartPlaybackEndpoint {}
@@ -30,11 +36,46 @@ impl<T: TimestampMarker> Endpoint for RecentlyPlayedTracksEndpoint<T> {
 }
 impl Endpoint for AddItemToQueueEndpoint {}
 
+impl_extra_params!(
+    TransferPlaybackEndpoint,
+    StartPlaybackEndpoint,
+    SeekToPositionEndpoint,
+    SetRepeatModeEndpoint,
+    SetPlaybackVolumeEndpoint,
+    ToggleShuffleEndpoint,
+    AddItemToQueueEndpoint
+);
+impl_extra_params!([T: TimestampMarker] RecentlyPlayedTracksEndpoint<T>);
+
+// Implement the `retryable` setter for the idempotent endpoints, which have
+// a `retryable` field.
+macro_rules! impl_retryable {
+    ($($t:ty),+ $(,)?) => {
+        $(impl $t {
+            /// Allow the request to be retried if it fails with a transient error,
+            /// as it's safe to repeat. See [`ClientConfig::max_retries`](crate::client::ClientConfig#structfield.max_retries).
+            pub fn retryable(mut self) -> Self {
+                self.retryable = true;
+                self
+            }
+        })+
+    };
+}
+
+impl_retryable!(
+    SeekToPositionEndpoint,
+    SetRepeatModeEndpoint,
+    SetPlaybackVolumeEndpoint,
+    ToggleShuffleEndpoint
+);
+
 pub async fn get_playback_state(
     market: Option<&str>,
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<PlaybackState> {
-    let market = market.map(|m| [("market", m)]);
+    let market = market
+        .or(spotify.config.market.as_deref())
+        .map(|m| [("market", m)]);
     spotify
         .get::<[(&str, &str); 1], _>("/me/player".to_owned(), market)
         .await
@@ -43,7 +84,7 @@ pub async fn get_playback_state(
 pub fn transfer_playback(device_id: impl Into<String>) -> TransferPlaybackEndpoint {
     TransferPlaybackEndpoint {
         device_ids: vec![device_id.into()],
-        play: None,
+        ..Default::default()
     }
 }
 
@@ -56,11 +97,32 @@ pub async fn get_available_devices(
         .map(|d: Devices| d.devices)
 }
 
+/// Get the user's currently active device, which is where playback commands
+/// are sent when no device ID is given.
+///
+/// This can be used to check whether playback can be controlled before doing
+/// so, and will fail with [`Error::NoActiveDevice`] if no device is active.
+/// This error is raised locally, so it has no
+/// [`status_code`](Error::status_code) and isn't a "not found" error.
+pub async fn get_active_device(spotify: &Client<impl AuthFlow + Authorised>) -> Result<Device> {
+    get_available_devices(spotify)
+        .await?
+        .into_iter()
+        .find(|d| d.is_active)
+        .ok_or(Error::NoActiveDevice)
+}
+
+/// Get the item currently being played on the user's account.
+///
+/// Returns `None` if nothing is playing, in which case Spotify responds with
+/// `204 No Content`.
 pub async fn get_currently_playing_track(
     market: Option<&str>,
     spotify: &Client<impl AuthFlow + Authorised>,
-) -> Result<CurrentlyPlayingItem> {
-    let market = market.map(|m| [("market", m)]);
+) -> Result<Option<CurrentlyPlayingItem>> {
+    let market = market
+        .or(spotify.config.market.as_deref())
+        .map(|m| [("market", m)]);
     spotify
         .get::<Option<[(&str, &str); 1]>, _>("/me/player/currently-playing".to_owned(), market)
         .await
@@ -70,6 +132,40 @@ pub fn start_playback() -> StartPlaybackEndpoint {
     StartPlaybackEndpoint::default()
 }
 
+/// Start playing an episode on the user's current (or specified) device, from
+/// where the user left off.
+///
+/// The position is read from the episode's [resume point](crate::model::ResumePoint),
+/// and if the episode was fully played (or it has no resume point), it's played
+/// from the beginning. Reading the resume point requires the
+/// [`user-read-playback-position`](crate::Scope::UserReadPlaybackPosition) scope.
+pub async fn resume_episode(
+    episode_id: impl Into<String>,
+    device_id: Option<&str>,
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<Nil> {
+    let episode = super::show::episode(episode_id).get(spotify).await?;
+    let position_ms = episode
+        .resume_point
+        .filter(|r| !r.fully_played)
+        .map_or(0, |r| r.resume_position_ms);
+
+    let mut request = start_playback()
+        .uris(&[&episode.uri])
+        .position_ms(position_ms);
+
+    if let Some(device_id) = device_id {
+        request = request.device_id(device_id);
+    }
+
+    request.send(spotify).await
+}
+
+/// Pause playback on the user's current (or specified) device.
+///
+/// If there's no active device and none is specified, this will return an
+/// [`Error::NoActiveDevice`], and if the user doesn't have Spotify Premium,
+/// an [`Error::PremiumRequired`].
 pub async fn pause_playback(
     device_id: Option<&str>,
     spotify: &Client<impl AuthFlow + Authorised>,
@@ -80,6 +176,11 @@ pub async fn pause_playback(
         .await
 }
 
+/// Skip to the next item in the user's queue.
+///
+/// If there's no active device and none is specified, this will return an
+/// [`Error::NoActiveDevice`], and if the user doesn't have Spotify Premium,
+/// an [`Error::PremiumRequired`].
 pub async fn skip_to_next(
     device_id: Option<&str>,
     spotify: &Client<impl AuthFlow + Authorised>,
@@ -90,6 +191,11 @@ pub async fn skip_to_next(
         .await
 }
 
+/// Skip to the previous item in the user's playback.
+///
+/// If there's no active device and none is specified, this will return an
+/// [`Error::NoActiveDevice`], and if the user doesn't have Spotify Premium,
+/// an [`Error::PremiumRequired`].
 pub async fn skip_to_previous(
     device_id: Option<&str>,
     spotify: &Client<impl AuthFlow + Authorised>,
@@ -108,28 +214,70 @@ pub async fn skip_to_previous(
 pub fn seek_to_position(position: u32) -> SeekToPositionEndpoint {
     SeekToPositionEndpoint {
         position_ms: position,
-        device_id: None,
+        ..Default::default()
     }
 }
 
+/// Seek to a position in the currently playing item, given as a fraction of
+/// its duration (e.g. `0.5` seeks to the middle of the item).
+///
+/// The fraction is clamped between `0.0` - `1.0`.
+///
+/// This will make an additional request to get the currently playing item,
+/// and will fail with [`Error::NothingPlaying`] if nothing is playing, or with
+/// [`Error::UnknownDuration`] if the duration of the item isn't known.
+pub async fn seek_to_fraction(
+    fraction: f64,
+    device_id: Option<&str>,
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<Nil> {
+    let item = get_currently_playing_track(None, spotify)
+        .await?
+        .and_then(|currently_playing| currently_playing.item);
+
+    let duration_ms = match item.ok_or(Error::NothingPlaying)? {
+        PlayableItem::Track(track) => track.duration_ms,
+        PlayableItem::Episode(episode) => episode.duration_ms,
+        _ => return Err(Error::UnknownDuration),
+    };
+
+    let position = (f64::from(duration_ms) * fraction.clamp(0.0, 1.0)).round() as u32;
+    let mut endpoint = seek_to_position(position);
+
+    if let Some(device_id) = device_id {
+        endpoint = endpoint.device_id(device_id);
+    }
+
+    endpoint.send(spotify).await
+}
+
 pub fn set_repeat_mode(repeat_mode: RepeatMode) -> SetRepeatModeEndpoint {
     SetRepeatModeEndpoint {
         state: repeat_mode,
-        device_id: None,
+        ..Default::default()
     }
 }
 
-pub fn set_playback_volume(volume: u32) -> SetPlaybackVolumeEndpoint {
+/// Set the volume of the user's current (or specified) playback device.
+///
+/// If the device doesn't allow its volume to be controlled, this will return an
+/// [`Error::VolumeNotSupported`]. To check beforehand, see
+/// [`Device::can_set_volume`].
+///
+/// The volume is a percentage, created from a number with
+/// [`Volume::try_from`], which fails if it's greater than `100`, or
+/// [`Volume::clamped`].
+pub fn set_playback_volume(volume: Volume) -> SetPlaybackVolumeEndpoint {
     SetPlaybackVolumeEndpoint {
         volume_percent: volume,
-        device_id: None,
+        ..Default::default()
     }
 }
 
 pub fn toggle_playback_shuffle(shuffle: bool) -> ToggleShuffleEndpoint {
     ToggleShuffleEndpoint {
         state: shuffle,
-        device_id: None,
+        ..Default::default()
     }
 }
 
@@ -146,7 +294,57 @@ pub async fn get_user_queue(spotify: &Client<impl AuthFlow + Authorised>) -> Res
 pub fn add_item_to_queue(uri: impl Into<String>) -> AddItemToQueueEndpoint {
     AddItemToQueueEndpoint {
         uri: uri.into(),
-        device_id: None,
+        ..Default::default()
+    }
+}
+
+/// Add multiple items to the end of the user's playback queue, in order.
+///
+/// Spotify only allows adding one item per request, so this makes one request
+/// per item, with a short delay between them. Failing to add an item doesn't
+/// stop the remaining items from being added; the failures are reported in the
+/// returned [`QueueSummary`].
+pub async fn add_items_to_queue<T: AsRef<str>>(
+    uris: &[T],
+    device_id: Option<&str>,
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<QueueSummary> {
+    let mut summary = QueueSummary::default();
+
+    for (i, uri) in uris.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(QUEUE_INTERVAL).await;
+        }
+
+        let mut endpoint = add_item_to_queue(uri.as_ref());
+
+        if let Some(device_id) = device_id {
+            endpoint = endpoint.device_id(device_id);
+        }
+
+        match endpoint.send(spotify).await {
+            Ok(_) => summary.added += 1,
+            Err(err) => summary.failed.push((uri.as_ref().to_owned(), err)),
+        }
+    }
+
+    Ok(summary)
+}
+
+/// The result of adding multiple items to the queue.
+#[derive(Debug, Default)]
+pub struct QueueSummary {
+    /// The number of items successfully added to the queue.
+    pub added: usize,
+    /// The URIs of the items that couldn't be added, and the error that
+    /// occurred for each.
+    pub failed: Vec<(String, Error)>,
+}
+
+impl QueueSummary {
+    /// Whether or not all the items were added to the queue.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
     }
 }
 
@@ -187,6 +385,8 @@ pub struct Unspecified;
 pub struct TransferPlaybackEndpoint {
     pub(crate) device_ids: Vec<String>,
     pub(crate) play: Option<bool>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl TransferPlaybackEndpoint {
@@ -199,7 +399,9 @@ impl TransferPlaybackEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Nil> {
-        spotify.put("/me/player".to_owned(), Body::Json(self)).await
+        let endpoint = with_extra_params("/me/player".to_owned(), &self.extra_params);
+
+        spotify.put(endpoint, Body::Json(self)).await
     }
 }
 
@@ -211,6 +413,8 @@ pub struct StartPlaybackEndpoint {
     pub(crate) uris: Option<Vec<String>>,
     pub(crate) offset: Option<Value>,
     pub(crate) position_ms: Option<u32>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl StartPlaybackEndpoint {
@@ -257,6 +461,7 @@ impl StartPlaybackEndpoint {
             Some(ref id) => format!("/me/player/play?device_id={id}"),
             None => "/me/player/play".to_owned(),
         };
+        let endpoint = with_extra_params(endpoint, &self.extra_params);
 
         spotify.put(endpoint, Body::Json(self)).await
     }
@@ -266,6 +471,10 @@ impl StartPlaybackEndpoint {
 pub struct SeekToPositionEndpoint {
     pub(crate) position_ms: u32,
     pub(crate) device_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) retryable: bool,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SeekToPositionEndpoint {
@@ -277,8 +486,16 @@ impl SeekToPositionEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Nil> {
+        let retryable = self.retryable;
+
         spotify
-            .request(Method::PUT, "/me/player/seek".to_owned(), self.into(), None)
+            .request_retryable(
+                Method::PUT,
+                "/me/player/seek".to_owned(),
+                self.into(),
+                None,
+                retryable,
+            )
             .await
     }
 }
@@ -287,6 +504,10 @@ impl SeekToPositionEndpoint {
 pub struct SetRepeatModeEndpoint {
     pub(crate) state: RepeatMode,
     pub(crate) device_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) retryable: bool,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SetRepeatModeEndpoint {
@@ -298,12 +519,15 @@ impl SetRepeatModeEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Nil> {
+        let retryable = self.retryable;
+
         spotify
-            .request(
+            .request_retryable(
                 Method::PUT,
                 "/me/player/repeat".to_owned(),
                 self.into(),
                 None,
+                retryable,
             )
             .await
     }
@@ -311,8 +535,12 @@ impl SetRepeatModeEndpoint {
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct SetPlaybackVolumeEndpoint {
-    pub(crate) volume_percent: u32,
+    pub(crate) volume_percent: Volume,
     pub(crate) device_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) retryable: bool,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SetPlaybackVolumeEndpoint {
@@ -324,12 +552,15 @@ impl SetPlaybackVolumeEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Nil> {
+        let retryable = self.retryable;
+
         spotify
-            .request(
+            .request_retryable(
                 Method::PUT,
                 "/me/player/volume".to_owned(),
                 self.into(),
                 None,
+                retryable,
             )
             .await
     }
@@ -339,6 +570,10 @@ impl SetPlaybackVolumeEndpoint {
 pub struct ToggleShuffleEndpoint {
     pub(crate) state: bool,
     pub(crate) device_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) retryable: bool,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ToggleShuffleEndpoint {
@@ -350,12 +585,15 @@ impl ToggleShuffleEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Nil> {
+        let retryable = self.retryable;
+
         spotify
-            .request(
+            .request_retryable(
                 Method::PUT,
                 "/me/player/shuffle".to_owned(),
                 self.into(),
                 None,
+                retryable,
             )
             .await
     }
@@ -366,35 +604,62 @@ pub struct RecentlyPlayedTracksEndpoint<T: TimestampMarker = Unspecified> {
     pub(crate) limit: Option<u32>,
     pub(crate) after: Option<u64>,
     pub(crate) before: Option<u64>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
+    #[serde(skip)]
     marker: PhantomData<T>,
 }
 
 impl RecentlyPlayedTracksEndpoint<Unspecified> {
-    /// A Unix timestamp in miliseconds. Returns all items after (but not including) this cursor position.
+    /// A Unix timestamp in milliseconds. Returns all items after (but not including) this cursor position.
     pub fn after(self, after: u64) -> RecentlyPlayedTracksEndpoint<After> {
         RecentlyPlayedTracksEndpoint {
             limit: self.limit,
             after: Some(after),
             before: self.before,
+            extra_params: self.extra_params,
             marker: PhantomData,
         }
     }
 
-    /// A Unix timestamp in miliseconds. Returns all items before (but not including) this cursor position.
+    /// A Unix timestamp in milliseconds. Returns all items before (but not including) this cursor position.
     pub fn before(self, before: u64) -> RecentlyPlayedTracksEndpoint<Before> {
         RecentlyPlayedTracksEndpoint {
             limit: self.limit,
             after: self.after,
             before: Some(before),
+            extra_params: self.extra_params,
             marker: PhantomData,
         }
     }
+
+    /// Returns all items after (but not including) this date and time.
+    ///
+    /// It's the same as [`after`](Self::after), but it takes a [`DateTime`] or
+    /// [`SystemTime`](std::time::SystemTime) instead of a Unix timestamp.
+    pub fn after_time(
+        self,
+        after: impl Into<DateTime<Utc>>,
+    ) -> RecentlyPlayedTracksEndpoint<After> {
+        self.after(unix_millis(after.into()))
+    }
+
+    /// Returns all items before (but not including) this date and time.
+    ///
+    /// It's the same as [`before`](Self::before), but it takes a [`DateTime`] or
+    /// [`SystemTime`](std::time::SystemTime) instead of a Unix timestamp.
+    pub fn before_time(
+        self,
+        before: impl Into<DateTime<Utc>>,
+    ) -> RecentlyPlayedTracksEndpoint<Before> {
+        self.before(unix_millis(before.into()))
+    }
 }
 
 impl<T: TimestampMarker + Default> RecentlyPlayedTracksEndpoint<T> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -409,10 +674,18 @@ impl<T: TimestampMarker + Default> RecentlyPlayedTracksEndpoint<T> {
     }
 }
 
+// Convert a date and time to a Unix timestamp in milliseconds, clamping
+// dates before the Unix epoch to `0`.
+fn unix_millis(date_time: DateTime<Utc>) -> u64 {
+    u64::try_from(date_time.timestamp_millis()).unwrap_or(0)
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct AddItemToQueueEndpoint {
     pub(crate) uri: String,
     pub(crate) device_id: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AddItemToQueueEndpoint {
@@ -434,3 +707,153 @@ impl AddItemToQueueEndpoint {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockTransport;
+
+    #[tokio::test]
+    async fn active_device_fails_without_status_when_none_is_active() {
+        let transport = MockTransport::new(|_| {
+            let devices = serde_json::json!({
+                "devices": [{
+                    "id": "device",
+                    "is_active": false,
+                    "is_private_session": false,
+                    "is_restricted": false,
+                    "name": "Speaker",
+                    "type": "Speaker",
+                    "volume_percent": 50,
+                    "supports_volume": true
+                }]
+            });
+
+            (200, devices.to_string())
+        });
+        let spotify = transport.client();
+
+        let error = get_active_device(&spotify).await.unwrap_err();
+
+        assert!(matches!(error, Error::NoActiveDevice));
+        assert_eq!(error.status_code(), None);
+        assert!(!error.is_not_found());
+    }
+
+    #[tokio::test]
+    async fn extra_params_are_added_to_the_query() {
+        let transport = MockTransport::new(|req| {
+            if req
+                .path_and_query
+                .starts_with("/v1/me/player/recently-played")
+            {
+                let page = serde_json::json!({
+                    "href": "https://api.spotify.com/v1/me/player/recently-played",
+                    "limit": 20,
+                    "next": null,
+                    "cursors": null,
+                    "items": []
+                });
+
+                return (200, page.to_string());
+            }
+
+            (204, String::new())
+        });
+        let spotify = transport.client();
+
+        seek_to_position(1000)
+            .extra_param("new_param", "value")
+            .send(&spotify)
+            .await
+            .unwrap();
+        recently_played_tracks()
+            .after(1)
+            .extra_param("new_param", "value")
+            .get(&spotify)
+            .await
+            .unwrap();
+        start_playback()
+            .device_id("device")
+            .context_uri("spotify:album:album")
+            .extra_param("new_param", "a value")
+            .send(&spotify)
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+
+        for request in &requests {
+            // The extra parameter is added after the endpoint's own ones.
+            let last_param = request.path_and_query.rsplit(['?', '&']).next();
+            assert!(last_param.unwrap().starts_with("new_param="), "{request:?}");
+            assert!(request.query("new_param").is_some());
+        }
+
+        assert_eq!(requests[0].query("position_ms").as_deref(), Some("1000"));
+        assert_eq!(requests[1].query("after").as_deref(), Some("1"));
+        assert_eq!(requests[2].query("device_id").as_deref(), Some("device"));
+        assert_eq!(requests[2].query("new_param").as_deref(), Some("a value"));
+        // The body only contains the endpoint's own parameters.
+        let body: Value = serde_json::from_str(requests[2].body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            json!({ "context_uri": "spotify:album:album", "uris": null, "offset": null, "position_ms": null })
+        );
+    }
+
+    #[tokio::test]
+    async fn currently_playing_is_none_when_nothing_is_playing() {
+        let transport = MockTransport::new(|_| (204, String::new()));
+        let spotify = transport.client();
+
+        let currently_playing = get_currently_playing_track(None, &spotify).await.unwrap();
+
+        assert!(currently_playing.is_none());
+    }
+
+    #[tokio::test]
+    async fn seek_to_fraction_fails_when_nothing_is_playing() {
+        let transport = MockTransport::new(|_| (204, String::new()));
+        let spotify = transport.client();
+
+        let result = seek_to_fraction(0.5, None, &spotify).await;
+
+        assert!(matches!(result, Err(Error::NothingPlaying)));
+        // Nothing is seeked without knowing the duration.
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn empty_response_still_fails_for_required_data() {
+        let transport = MockTransport::new(|_| (204, String::new()));
+        let spotify = transport.client();
+
+        let result = get_available_devices(&spotify).await;
+
+        assert!(matches!(result, Err(Error::Deserialization { .. })));
+    }
+
+    #[tokio::test]
+    async fn volume_over_100_is_rejected() {
+        let transport = MockTransport::new(|_| (204, String::new()));
+        let spotify = transport.client();
+
+        let result = Volume::try_from(101);
+        assert!(matches!(result, Err(Error::InvalidArgument { .. })));
+
+        set_playback_volume(Volume::try_from(100).unwrap())
+            .send(&spotify)
+            .await
+            .unwrap();
+        set_playback_volume(Volume::clamped(101))
+            .send(&spotify)
+            .await
+            .unwrap();
+
+        for request in transport.requests() {
+            assert_eq!(request.query("volume_percent").as_deref(), Some("100"));
+        }
+    }
+}
@@ -1,22 +1,32 @@
+use std::path::Path;
+
 use base64::{engine::general_purpose, Engine};
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use futures_util::{stream, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{json, Value};
 
 use crate::{
     auth::{AuthFlow, Authorised},
     client::Body,
-    error::Result,
+    error::{Error, Result},
     model::{
+        is_local_uri,
         playlist::{
             FeaturedPlaylists, Playlist, PlaylistItem, Playlists, SimplifiedPlaylist, SnapshotId,
+            SnapshotResponse,
         },
-        Image, Page,
+        Image, Page, PlayableItem,
     },
-    Nil,
+    query_list, Nil,
 };
 
-use super::{Client, Endpoint, EndpointPrivate};
+use super::{with_extra_params, Client, Endpoint, EndpointPrivate, ExtraParams, MAX_LIMIT};
+
+// Every JPEG file starts with these bytes (the SOI marker).
+const JPEG_MAGIC_BYTES: [u8; 2] = [0xFF, 0xD8];
+// The maximum size of a playlist cover, after being Base64 encoded.
+const MAX_COVER_SIZE: usize = 256 * 1024;
 
 impl Endpoint for PlaylistEndpoint {}
 impl Endpoint for ChangePlaylistDetailsEndpoint {}
@@ -30,6 +40,30 @@ impl Endpoint for CreatePlaylistEndpoint<'_> {}
 impl Endpoint for FeaturedPlaylistsEndpoint {}
 impl Endpoint for CategoryPlaylistsEndpoint {}
 
+impl_extra_params!(
+    PlaylistEndpoint,
+    PlaylistItemsEndpoint,
+    CurrentUserPlaylistsEndpoint,
+    UserPlaylistsEndpoint,
+    FeaturedPlaylistsEndpoint,
+    CategoryPlaylistsEndpoint,
+    ChangePlaylistDetailsEndpoint,
+    UpdatePlaylistItemsEndpoint,
+    AddPlaylistItemsEndpoint,
+    RemovePlaylistItemsEndpoint
+);
+impl_extra_params!(['a] CreatePlaylistEndpoint<'a>);
+
+// The maximum amount of items that can be added to a playlist in one request.
+const MAX_PLAYLIST_ITEMS: usize = 100;
+// The maximum limit when getting a playlist's items.
+const PLAYLIST_ITEMS_MAX_LIMIT: u32 = 100;
+// The item types requested by default, so episodes aren't returned as tracks.
+const DEFAULT_ADDITIONAL_TYPES: &str = "track,episode";
+// The maximum amount of playlists fetched at once by `get_playlists`, if the
+// client doesn't limit its concurrency.
+const DEFAULT_MAX_CONCURRENT_FETCHES: usize = 8;
+
 pub fn playlist(id: impl Into<String>) -> PlaylistEndpoint {
     PlaylistEndpoint {
         id: id.into(),
@@ -37,6 +71,32 @@ pub fn playlist(id: impl Into<String>) -> PlaylistEndpoint {
     }
 }
 
+/// Get several playlists at once, returning a result for each playlist, in the
+/// same order as the IDs, so a playlist that can't be fetched doesn't fail
+/// the others.
+///
+/// As Spotify doesn't allow getting several playlists in one request, they're
+/// requested concurrently, bounded by the client's limit if one was set using
+/// [`Client::with_max_concurrency`](crate::client::Client::with_max_concurrency),
+/// or otherwise at most 8 at a time.
+pub async fn get_playlists<T: AsRef<str>>(
+    ids: &[T],
+    spotify: &Client<impl AuthFlow>,
+) -> Vec<Result<Playlist>> {
+    // The client's limit applies to every request, so there's no need for
+    // another one.
+    let max_concurrency = match spotify.concurrency_limit {
+        Some(_) => ids.len().max(1),
+        None => DEFAULT_MAX_CONCURRENT_FETCHES,
+    };
+
+    stream::iter(ids)
+        .map(|id| playlist(id.as_ref()).get(spotify))
+        .buffered(max_concurrency)
+        .collect()
+        .await
+}
+
 pub fn change_playlist_details(id: impl Into<String>) -> ChangePlaylistDetailsEndpoint {
     ChangePlaylistDetailsEndpoint {
         id: id.into(),
@@ -53,6 +113,15 @@ pub fn playlist_items(id: impl Into<String>) -> PlaylistItemsEndpoint {
 
 // split into two functions: replace and reoder playlist items
 // (the endpoint serves two functions)?
+/// Reorder or replace the items of a playlist.
+///
+/// When reordering, `range_start` and `insert_before` are sent to Spotify
+/// unchanged, and both are positions in the playlist *before* it's reordered.
+/// Items are moved by position, not by URI, so a range containing the same
+/// track more than once is moved as is, keeping the relative order of the items.
+///
+/// For example, reordering the playlist `[A, B, A, C]` with `range_start` 0,
+/// `range_length` 2 and `insert_before` 4 results in `[A, C, A, B]`.
 pub fn update_playlist_items(
     id: impl Into<String>,
     range_start: u32,
@@ -66,37 +135,124 @@ pub fn update_playlist_items(
     }
 }
 
+/// Add items to a playlist.
+///
+/// Local files (see [`is_local_uri`]) can't be added, so they're
+/// skipped rather than sent to Spotify, and can be retrieved using
+/// [`AddPlaylistItemsEndpoint::skipped_uris`]. If there's nothing left to add,
+/// no items are sent, and the current snapshot ID of the playlist is returned.
 pub fn add_items_to_playlist<T: ToString>(
     id: impl Into<String>,
     item_uris: &[T],
 ) -> AddPlaylistItemsEndpoint {
+    let (skipped_uris, uris) = split_local_uris(item_uris.iter().map(ToString::to_string));
+
     AddPlaylistItemsEndpoint {
         id: id.into(),
-        uris: item_uris.iter().map(ToString::to_string).collect(),
-        position: None,
+        uris,
+        skipped_uris,
+        ..Default::default()
+    }
+}
+
+/// Add any number of items to a playlist, returning the final snapshot ID.
+///
+/// The items are added in chunks of 100 (the maximum Spotify allows per
+/// request), one request after another. If a position is given, each chunk is
+/// inserted right after the previous one, so the items keep their order.
+/// Otherwise, they're appended to the playlist.
+///
+/// If a request fails, the chunks before it have already been added.
+/// Local files are skipped, like with [`add_items_to_playlist`].
+pub async fn add_items_to_playlist_all<T: ToString>(
+    id: impl Into<String>,
+    item_uris: &[T],
+    position: Option<u32>,
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<SnapshotId> {
+    let id = id.into();
+
+    if item_uris.is_empty() {
+        return get_playlist_snapshot_id(id, spotify).await;
+    }
+
+    let mut position = position;
+    let mut snapshot_id = None;
+
+    for chunk in item_uris.chunks(MAX_PLAYLIST_ITEMS) {
+        let mut request = add_items_to_playlist(&id, chunk);
+        let added = request.uris.len() as u32;
+
+        // The chunk only contained local files, which are skipped.
+        if added == 0 {
+            continue;
+        }
+
+        if let Some(position) = position {
+            request = request.position(position);
+        }
+
+        snapshot_id = Some(request.send(spotify).await?);
+        // The next chunk is inserted after the items that were just added.
+        position = position.map(|p| p + added);
+    }
+
+    match snapshot_id {
+        Some(snapshot_id) => Ok(snapshot_id),
+        None => get_playlist_snapshot_id(id, spotify).await,
     }
 }
 
+/// Remove items from a playlist.
+///
+/// Local files (see [`is_local_uri`]) can't be removed using their URI,
+/// so they're skipped rather than sent to Spotify, and can be retrieved using
+/// [`RemovePlaylistItemsEndpoint::skipped_uris`]. If there's nothing left to
+/// remove, no items are sent, and the current snapshot ID of the playlist is
+/// returned.
 pub fn remove_playlist_items<T: AsRef<str>>(
     id: impl Into<String>,
     item_uris: &[T],
 ) -> RemovePlaylistItemsEndpoint {
-    let tracks = item_uris
-        .iter()
-        .map(|u| json!({ "uri": u.as_ref() }))
-        .collect();
+    let (skipped_uris, uris) = split_local_uris(item_uris.iter().map(|u| u.as_ref().to_owned()));
+    let tracks = uris.iter().map(|u| json!({ "uri": u })).collect();
 
     RemovePlaylistItemsEndpoint {
         id: id.into(),
         tracks,
-        snapshot_id: None,
+        skipped_uris,
+        ..Default::default()
+    }
+}
+
+// Split URIs into the local ones, which can't be sent to Spotify, and the rest.
+fn split_local_uris(uris: impl Iterator<Item = String>) -> (Vec<String>, Vec<String>) {
+    let (local, uris): (Vec<_>, Vec<_>) = uris.partition(|u| is_local_uri(u));
+
+    if !local.is_empty() {
+        tracing::warn!(
+            count = local.len(),
+            "Skipping local files, which can't be sent to Spotify."
+        );
     }
+
+    (local, uris)
 }
 
+/// Get the playlists owned or followed by the current user.
+///
+/// Private playlists are only included with the
+/// [`playlist-read-private`](crate::Scope::PlaylistReadPrivate) scope, and
+/// collaborative playlists with the
+/// [`playlist-read-collaborative`](crate::Scope::PlaylistReadCollaborative) scope.
 pub fn current_user_playlists() -> CurrentUserPlaylistsEndpoint {
     CurrentUserPlaylistsEndpoint::default()
 }
 
+/// Get the public playlists owned or followed by a user.
+///
+/// If the user is the current user, the scopes described in
+/// [`current_user_playlists`] apply.
 pub fn user_playlists(user_id: impl Into<String>) -> UserPlaylistsEndpoint {
     UserPlaylistsEndpoint {
         id: user_id.into(),
@@ -134,6 +290,143 @@ pub fn category_playlists(category_id: impl Into<String>) -> CategoryPlaylistsEn
     }
 }
 
+/// Get the current snapshot ID of a playlist, without getting the rest of
+/// the playlist.
+///
+/// This is useful to make changes against the latest version of a playlist,
+/// e.g. using a [`PlaylistSnapshot`](crate::model::playlist::PlaylistSnapshot).
+pub async fn get_playlist_snapshot_id(
+    id: impl Into<String>,
+    spotify: &Client<impl AuthFlow>,
+) -> Result<SnapshotId> {
+    spotify
+        .get(
+            format!("/playlists/{}", id.into()),
+            [("fields", "snapshot_id")],
+        )
+        .await
+        .map(|i: SnapshotResponse| i.snapshot_id)
+}
+
+/// Export all the items of a playlist in the given format, e.g. to back it up.
+///
+/// The name, artists, album, URI and the date each item was added at are
+/// exported. For episodes, the publisher and name of the show are used as the
+/// artists and album. Items that couldn't be deserialized are skipped.
+pub async fn export_playlist(
+    id: impl Into<String>,
+    format: ExportFormat,
+    spotify: &Client<impl AuthFlow>,
+) -> Result<String> {
+    let items = playlist_items(id)
+        .limit(PLAYLIST_ITEMS_MAX_LIMIT)
+        .get(spotify)
+        .await?
+        .get_remaining_with_limit(PLAYLIST_ITEMS_MAX_LIMIT, spotify)
+        .await?;
+
+    let rows = items.iter().flatten().filter_map(ExportRow::new);
+    let mut export = String::new();
+
+    match format {
+        ExportFormat::Csv => {
+            export.push_str("name,artists,album,uri,added_at\n");
+
+            for row in rows {
+                let fields = [
+                    row.name,
+                    &row.artists.join(", "),
+                    row.album,
+                    row.uri,
+                    &row.added_at.unwrap_or_default(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+
+                export.push_str(&fields.join(","));
+                export.push('\n');
+            }
+        }
+
+        ExportFormat::JsonLines => {
+            for row in rows {
+                let line = json!({
+                    "name": row.name,
+                    "artists": row.artists,
+                    "album": row.album,
+                    "uri": row.uri,
+                    "added_at": row.added_at,
+                });
+
+                export.push_str(&line.to_string());
+                export.push('\n');
+            }
+        }
+    }
+
+    Ok(export)
+}
+
+/// The format to [export a playlist](export_playlist) in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// Comma-separated values, with a header row. The artists of an item are
+    /// separated by commas.
+    Csv,
+    /// JSON Lines, i.e. one JSON object per line.
+    JsonLines,
+}
+
+// The exported fields of a playlist item.
+struct ExportRow<'a> {
+    name: &'a str,
+    artists: Vec<&'a str>,
+    album: &'a str,
+    uri: &'a str,
+    added_at: Option<String>,
+}
+
+impl<'a> ExportRow<'a> {
+    fn new(item: &'a PlaylistItem) -> Option<Self> {
+        let (name, artists, album, uri) = match &item.track {
+            PlayableItem::Track(track) => (
+                track.name.as_str(),
+                track.artists.iter().map(|a| a.name.as_str()).collect(),
+                track.album.name.as_str(),
+                track.uri.as_str(),
+            ),
+            PlayableItem::Episode(episode) => (
+                episode.name.as_str(),
+                vec![episode.show.publisher.as_str()],
+                episode.show.name.as_str(),
+                episode.uri.as_str(),
+            ),
+            PlayableItem::Unknown(_) => return None,
+        };
+
+        Some(Self {
+            name,
+            artists,
+            album,
+            uri,
+            added_at: item.added_at.map(|a| a.to_rfc3339()),
+        })
+    }
+}
+
+// Quote a CSV field if needed, escaping any quotes in it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Get the cover images of a playlist, without getting the rest of the playlist
+/// (e.g. to show thumbnails, using [`Images`](crate::model::Images)).
+///
+/// If the playlist has no cover (e.g. an empty playlist without a custom
+/// image), an empty list is returned.
 pub async fn get_playlist_image(
     id: impl Into<String>,
     spotify: &Client<impl AuthFlow>,
@@ -141,14 +434,38 @@ pub async fn get_playlist_image(
     spotify
         .get::<(), _>(format!("/playlists/{}/images", id.into()), None)
         .await
+        .map(|images: Option<Vec<Image>>| images.unwrap_or_default())
 }
 
+/// Upload a custom cover image for a playlist.
+///
+/// The image must be a JPEG, and its Base64 encoding can be at most 256 KB.
+/// Otherwise, an [`Error::InvalidArgument`] is returned without sending the
+/// request.
 pub async fn add_playlist_image(
     id: impl Into<String>,
     image: &[u8],
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<Nil> {
+    if !image.starts_with(&JPEG_MAGIC_BYTES) {
+        return Err(Error::InvalidArgument {
+            description: "The playlist cover must be a JPEG image.".to_owned(),
+        });
+    }
+
     let encoded_image = general_purpose::STANDARD.encode(image).into_bytes();
+
+    if encoded_image.len() > MAX_COVER_SIZE {
+        return Err(Error::InvalidArgument {
+            description: format!(
+                "The playlist cover is {} KB once Base64 encoded, but at most {} KB are allowed. \
+                 Try resizing the image or lowering its quality.",
+                encoded_image.len().div_ceil(1024),
+                MAX_COVER_SIZE / 1024
+            ),
+        });
+    }
+
     let body = <Body>::File(encoded_image);
 
     spotify
@@ -156,11 +473,32 @@ pub async fn add_playlist_image(
         .await
 }
 
+/// Upload a custom cover image for a playlist from a JPEG file.
+///
+/// The same restrictions as [`add_playlist_image`] apply, and an
+/// [`Error::Io`] is returned if the file can't be read.
+pub async fn add_playlist_image_from_path(
+    id: impl Into<String>,
+    path: impl AsRef<Path>,
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<Nil> {
+    let path = path.as_ref();
+    let image = tokio::fs::read(path).await.map_err(|source| Error::Io {
+        source,
+        path: path.to_owned(),
+    })?;
+
+    add_playlist_image(id, &image, spotify).await
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct PlaylistEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    pub(crate) additional_types: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl PlaylistEndpoint {
@@ -170,8 +508,18 @@ impl PlaylistEndpoint {
         self
     }
 
+    #[doc = include_str!("../docs/additional_types.md")]
+    pub fn additional_types<T: AsRef<str>>(mut self, additional_types: &[T]) -> Self {
+        self.additional_types = Some(query_list(additional_types));
+        self
+    }
+
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Playlist> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Playlist> {
+        self.market = spotify.market_or_default(self.market);
+        self.additional_types
+            .get_or_insert_with(|| DEFAULT_ADDITIONAL_TYPES.to_owned());
+
         spotify.get(format!("/playlists/{}", self.id), self).await
     }
 }
@@ -188,6 +536,8 @@ pub struct ChangePlaylistDetailsEndpoint {
     pub(crate) collaborative: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) description: Option<String>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ChangePlaylistDetailsEndpoint {
@@ -212,16 +562,25 @@ impl ChangePlaylistDetailsEndpoint {
     }
 
     /// The new description for the playlist.
+    ///
+    /// Only the details that are set are changed, so if this isn't called,
+    /// the description is left untouched. Setting it to an empty string
+    /// clears it, like [`clear_description`](Self::clear_description).
     pub fn description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
         self
     }
 
+    /// Clear the playlist's description.
+    pub fn clear_description(self) -> Self {
+        self.description("")
+    }
+
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Nil> {
-        spotify
-            .put(format!("/playlists/{}", self.id), self.json())
-            .await
+        let endpoint = with_extra_params(format!("/playlists/{}", self.id), &self.extra_params);
+
+        spotify.put(endpoint, self.json()).await
     }
 }
 
@@ -230,8 +589,12 @@ pub struct PlaylistItemsEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    pub(crate) fields: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    pub(crate) additional_types: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl PlaylistItemsEndpoint {
@@ -241,9 +604,12 @@ impl PlaylistItemsEndpoint {
         self
     }
 
-    #[doc = include_str!("../docs/limit.md")]
+    /// The maximum amount of items to return. Defaults to `20`.
+    ///
+    /// Unlike most endpoints, it can be up to `100`, and it's clamped between
+    /// `1` - `100`.
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, PLAYLIST_ITEMS_MAX_LIMIT));
         self
     }
 
@@ -253,8 +619,55 @@ impl PlaylistItemsEndpoint {
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Page<PlaylistItem>> {
+    /// Filters the fields of the response, e.g.
+    /// `href,limit,next,offset,previous,total,items(track(id,name))` to only
+    /// get the ID and name of each track, which makes the response much
+    /// smaller for large playlists. Read more about the syntax
+    /// [here](https://developer.spotify.com/documentation/web-api/reference/get-playlists-tracks).
+    ///
+    /// As the shape of the items depends on the fields, use
+    /// [`get_fields`](Self::get_fields) to get them.
+    pub fn fields(mut self, fields: impl Into<String>) -> Self {
+        self.fields = Some(fields.into());
+        self
+    }
+
+    #[doc = include_str!("../docs/additional_types.md")]
+    pub fn additional_types<T: AsRef<str>>(mut self, additional_types: &[T]) -> Self {
+        self.additional_types = Some(query_list(additional_types));
+        self
+    }
+
+    /// Send the request, returning the full items.
+    ///
+    /// If [`fields`](Self::fields) are set, they must include every field of
+    /// the page and its items, otherwise the response can't be deserialized.
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Page<PlaylistItem>> {
+        self.market = spotify.market_or_default(self.market);
+        self.additional_types
+            .get_or_insert_with(|| DEFAULT_ADDITIONAL_TYPES.to_owned());
+
+        spotify
+            .get(format!("/playlists/{}/tracks", self.id), self)
+            .await
+    }
+
+    /// Send the request, deserializing the items as `T`, which only needs to
+    /// contain the [`fields`](Self::fields) that were requested (use [`Value`]
+    /// to get them as JSON).
+    ///
+    /// The fields must still include every field of the page itself (`href`,
+    /// `limit`, `next`, `offset`, `previous` and `total`), otherwise the
+    /// response can't be deserialized. The next pages are requested with the
+    /// same fields.
+    pub async fn get_fields<T: Clone + DeserializeOwned>(
+        mut self,
+        spotify: &Client<impl AuthFlow>,
+    ) -> Result<Page<T>> {
+        self.market = spotify.market_or_default(self.market);
+        self.additional_types
+            .get_or_insert_with(|| DEFAULT_ADDITIONAL_TYPES.to_owned());
+
         spotify
             .get(format!("/playlists/{}/tracks", self.id), self)
             .await
@@ -273,6 +686,8 @@ pub struct UpdatePlaylistItemsEndpoint {
     pub(crate) range_length: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) snapshot_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl UpdatePlaylistItemsEndpoint {
@@ -301,11 +716,14 @@ impl UpdatePlaylistItemsEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<String> {
+    pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<SnapshotId> {
+        let endpoint =
+            with_extra_params(format!("/playlists/{}/tracks", self.id), &self.extra_params);
+
         spotify
-            .put(format!("/playlists/{}/tracks", self.id), self.json())
+            .put(endpoint, self.json())
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: SnapshotResponse| i.snapshot_id)
     }
 }
 
@@ -316,9 +734,18 @@ pub struct AddPlaylistItemsEndpoint {
     pub(crate) uris: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) position: Option<u32>,
+    #[serde(skip)]
+    pub(crate) skipped_uris: Vec<String>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl AddPlaylistItemsEndpoint {
+    /// The URIs of the local files that were skipped, as they can't be added.
+    pub fn skipped_uris(&self) -> &[String] {
+        &self.skipped_uris
+    }
+
     /// The position to insert the items at, zero-based. If omitted, items will be appended to the playlist.
     pub fn position(mut self, position: u32) -> Self {
         self.position = Some(position);
@@ -326,11 +753,19 @@ impl AddPlaylistItemsEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<String> {
+    pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<SnapshotId> {
+        // Spotify fails when no URIs are given (e.g. when they were all local).
+        if self.uris.is_empty() {
+            return get_playlist_snapshot_id(self.id, spotify).await;
+        }
+
+        let endpoint =
+            with_extra_params(format!("/playlists/{}/tracks", self.id), &self.extra_params);
+
         spotify
-            .post(format!("/playlists/{}/tracks", self.id), self.json())
+            .post(endpoint, self.json())
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: SnapshotResponse| i.snapshot_id)
     }
 }
 
@@ -341,9 +776,19 @@ pub struct RemovePlaylistItemsEndpoint {
     pub(crate) tracks: Vec<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) snapshot_id: Option<String>,
+    #[serde(skip)]
+    pub(crate) skipped_uris: Vec<String>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl RemovePlaylistItemsEndpoint {
+    /// The URIs of the local files that were skipped, as they can't be removed
+    /// using their URI.
+    pub fn skipped_uris(&self) -> &[String] {
+        &self.skipped_uris
+    }
+
     /// The playlist's snapshot ID against which to make changes.
     pub fn snapshot_id(mut self, snapshot_id: impl Into<String>) -> Self {
         self.snapshot_id = Some(snapshot_id.into());
@@ -351,11 +796,19 @@ impl RemovePlaylistItemsEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<String> {
+    pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<SnapshotId> {
+        // Spotify fails when no items are given (e.g. when they were all local).
+        if self.tracks.is_empty() {
+            return get_playlist_snapshot_id(self.id, spotify).await;
+        }
+
+        let endpoint =
+            with_extra_params(format!("/playlists/{}/tracks", self.id), &self.extra_params);
+
         spotify
-            .delete(format!("/playlists/{}/tracks", self.id), self.json())
+            .delete(endpoint, self.json())
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: SnapshotResponse| i.snapshot_id)
     }
 }
 
@@ -363,12 +816,14 @@ impl RemovePlaylistItemsEndpoint {
 pub struct CurrentUserPlaylistsEndpoint {
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl CurrentUserPlaylistsEndpoint {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -393,12 +848,14 @@ pub struct UserPlaylistsEndpoint {
     pub(crate) id: String,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl UserPlaylistsEndpoint {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -426,6 +883,8 @@ pub struct CreatePlaylistEndpoint<'a> {
     pub(crate) public: Option<bool>,
     pub(crate) collaborative: Option<bool>,
     pub(crate) description: Option<String>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl<'a> CreatePlaylistEndpoint<'a> {
@@ -468,10 +927,12 @@ impl<'a> CreatePlaylistEndpoint<'a> {
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Playlist> {
         let tracks = self.tracks;
+        let endpoint = with_extra_params(
+            format!("/users/{}/playlists", self.user_id),
+            &self.extra_params,
+        );
 
-        let mut playlist: Playlist = spotify
-            .post(format!("/users/{}/playlists", self.user_id), self.json())
-            .await?;
+        let mut playlist: Playlist = spotify.post(endpoint, self.json()).await?;
 
         if let Some(tracks) = tracks {
             add_items_to_playlist(&playlist.id, tracks)
@@ -493,6 +954,8 @@ pub struct FeaturedPlaylistsEndpoint {
     pub(crate) timestamp: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl FeaturedPlaylistsEndpoint {
@@ -516,7 +979,7 @@ impl FeaturedPlaylistsEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -541,6 +1004,8 @@ pub struct CategoryPlaylistsEndpoint {
     pub(crate) country: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl CategoryPlaylistsEndpoint {
@@ -552,7 +1017,7 @@ impl CategoryPlaylistsEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -570,3 +1035,140 @@ impl CategoryPlaylistsEndpoint {
             .map(|p: Playlists| p.playlists)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use reqwest::Method;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::client::mock::MockTransport;
+
+    #[tokio::test]
+    async fn playlist_items_fields_are_deserialized_as_requested() {
+        #[derive(Clone, Debug, Deserialize)]
+        struct Item {
+            track: Track,
+        }
+
+        #[derive(Clone, Debug, Deserialize)]
+        struct Track {
+            name: String,
+        }
+
+        let transport = MockTransport::new(|_| {
+            let page = json!({
+                "href": "https://api.spotify.com/v1/playlists/playlist/tracks",
+                "limit": 20,
+                "next": null,
+                "offset": 0,
+                "previous": null,
+                "total": 1,
+                "items": [{ "track": { "name": "Track" } }]
+            });
+
+            (200, page.to_string())
+        });
+        let spotify = transport.client();
+        let fields = "href,limit,next,offset,previous,total,items(track(name))";
+
+        let page = playlist_items("playlist")
+            .fields(fields)
+            .get_fields::<Item>(&spotify)
+            .await
+            .unwrap();
+        assert_eq!(page.filtered_items()[0].track.name, "Track");
+
+        let page = playlist_items("playlist")
+            .fields(fields)
+            .get_fields::<Value>(&spotify)
+            .await
+            .unwrap();
+        assert_eq!(
+            page.filtered_items()[0],
+            json!({ "track": { "name": "Track" } })
+        );
+
+        assert_eq!(
+            transport.requests()[0].query("fields").as_deref(),
+            Some(fields)
+        );
+    }
+
+    #[tokio::test]
+    async fn adding_only_local_files_sends_no_items() {
+        let transport =
+            MockTransport::new(|_| (200, json!({ "snapshot_id": "snapshot" }).to_string()));
+        let spotify = transport.client();
+
+        let request = add_items_to_playlist("playlist", &["spotify:local:a:b:c:1"]);
+        assert_eq!(request.skipped_uris(), ["spotify:local:a:b:c:1"]);
+
+        let snapshot_id = request.send(&spotify).await.unwrap();
+
+        assert_eq!(snapshot_id.as_str(), "snapshot");
+
+        // Only the current snapshot ID is requested.
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::GET);
+        assert_eq!(requests[0].query("fields").as_deref(), Some("snapshot_id"));
+    }
+
+    #[tokio::test]
+    async fn update_playlist_items_reorders_range_with_duplicates() {
+        let items = Arc::new(Mutex::new(vec!["A", "B", "A", "C"]));
+        let playlist = items.clone();
+
+        // Reorder the items by position, like Spotify does.
+        let transport = MockTransport::new(move |req| {
+            let body: Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+            let range_start = body["range_start"].as_u64().unwrap() as usize;
+            let range_length = body["range_length"].as_u64().unwrap_or(1) as usize;
+            let insert_before = body["insert_before"].as_u64().unwrap() as usize;
+
+            let mut items = playlist.lock().unwrap();
+            let moved: Vec<_> = items
+                .drain(range_start..range_start + range_length)
+                .collect();
+            let insert_at = if insert_before > range_start {
+                insert_before - range_length
+            } else {
+                insert_before
+            };
+            items.splice(insert_at..insert_at, moved);
+
+            (200, json!({ "snapshot_id": "snapshot2" }).to_string())
+        });
+        let spotify = transport.client();
+
+        let snapshot_id = update_playlist_items("playlist", 0, 4)
+            .range_length(2)
+            .snapshot_id("snapshot1")
+            .send(&spotify)
+            .await
+            .unwrap();
+
+        assert_eq!(snapshot_id.as_str(), "snapshot2");
+        assert_eq!(*items.lock().unwrap(), ["A", "C", "A", "B"]);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::PUT);
+        assert_eq!(requests[0].path_and_query, "/v1/playlists/playlist/tracks");
+
+        // The positions are sent unchanged, and no URIs are sent when reordering.
+        let body: Value = serde_json::from_str(requests[0].body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "range_start": 0,
+                "insert_before": 4,
+                "range_length": 2,
+                "snapshot_id": "snapshot1"
+            })
+        );
+    }
+}
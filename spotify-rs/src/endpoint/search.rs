@@ -7,14 +7,24 @@ use crate::{
     query_list,
 };
 
-use super::{Client, Endpoint};
+use super::{Client, Endpoint, ExtraParams, MAX_LIMIT};
 
 impl Endpoint for SearchEndpoint {}
 
+impl_extra_params!(SearchEndpoint);
+
+// The maximum offset a search accepts.
+const SEARCH_MAX_OFFSET: u32 = 1000;
+
 /// Search for an item. The query can be either a string or
 /// [`SearchQuery`](crate::model::search::SearchQuery). More details about
 /// search queries can be found
 /// [here](https://developer.spotify.com/documentation/web-api/reference/search).
+///
+/// When a market is set, [Track Relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
+/// is applied to the tracks in the results, so they're playable in that market,
+/// and a relinked track's [`linked_from`](crate::model::track::Track::linked_from)
+/// refers to the track that was originally found.
 pub fn search(query: impl Into<SearchQuery>, item_types: &[Item]) -> SearchEndpoint {
     let r#type = query_list(item_types);
     let query = query.into().to_string();
@@ -38,7 +48,9 @@ pub struct SearchEndpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) offset: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) include_external: Option<bool>,
+    pub(crate) include_external: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SearchEndpoint {
@@ -48,15 +60,29 @@ impl SearchEndpoint {
         self
     }
 
-    #[doc = include_str!("../docs/limit.md")]
+    /// Sets the market to the one of the current user (the country associated
+    /// with the access token), so the tracks in the results are playable for them.
+    ///
+    /// This requires a user access token (i.e. not the Client Credentials flow).
+    pub fn market_from_token(self) -> Self {
+        self.market("from_token")
+    }
+
+    /// The maximum amount of items to return for *each* item type searched
+    /// for, e.g. a limit of `10` when searching for tracks and albums returns
+    /// up to 10 tracks and 10 albums. Defaults to `20`.
+    ///
+    /// The limit is clamped between `1` - `50`.
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
-    #[doc = include_str!("../docs/offset.md")]
+    /// The index of the first item to return, which is clamped to `1000`, the
+    /// maximum a search accepts (unlike other endpoints, whose offsets aren't
+    /// capped).
     pub fn offset(mut self, offset: u32) -> Self {
-        self.offset = Some(offset);
+        self.offset = Some(offset.min(SEARCH_MAX_OFFSET));
         self
     }
 
@@ -65,10 +91,18 @@ impl SearchEndpoint {
     ///
     /// By default externally hosted audio content is marked as unplayable in the response.
     pub fn include_external(mut self, include_external: bool) -> Self {
-        self.include_external = Some(include_external);
+        self.include_external = include_external.then(|| "audio".to_owned());
         self
     }
 
+    /// Signals that the client can play externally hosted audio content,
+    /// and marks the content as playable in the response.
+    ///
+    /// This is the same as calling [`include_external`](Self::include_external) with `true`.
+    pub fn include_external_audio(self) -> Self {
+        self.include_external(true)
+    }
+
     /// Allows you to change the types of items to search.
     pub fn item_types(mut self, item_types: &[Item]) -> Self {
         self.r#type = query_list(item_types);
@@ -76,7 +110,53 @@ impl SearchEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<SearchResults> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<SearchResults> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get("/search".to_owned(), self).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Serialize the endpoint into a query string, the same way the client does.
+    fn query_pairs(endpoint: &SearchEndpoint) -> Vec<(String, String)> {
+        let request = reqwest::Client::new()
+            .get("https://api.spotify.com/v1/search")
+            .query(endpoint)
+            .build()
+            .unwrap();
+
+        request.url().query_pairs().into_owned().collect()
+    }
+
+    fn param(pairs: &[(String, String)], key: &str) -> Option<String> {
+        pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn search_query_includes_market_when_set() {
+        let pairs = query_pairs(&search("daft punk", &[Item::Track]).market("GB"));
+
+        assert_eq!(param(&pairs, "q").as_deref(), Some("daft punk"));
+        assert_eq!(param(&pairs, "type").as_deref(), Some("track"));
+        assert_eq!(param(&pairs, "market").as_deref(), Some("GB"));
+    }
+
+    #[test]
+    fn search_query_includes_market_from_token() {
+        let pairs = query_pairs(&search("daft punk", &[Item::Track]).market_from_token());
+
+        assert_eq!(param(&pairs, "market").as_deref(), Some("from_token"));
+    }
+
+    #[test]
+    fn search_query_omits_market_when_unset() {
+        let pairs = query_pairs(&search("daft punk", &[Item::Track, Item::Album]));
+
+        assert_eq!(param(&pairs, "type").as_deref(), Some("track,album"));
+        assert_eq!(param(&pairs, "market"), None);
+    }
+}
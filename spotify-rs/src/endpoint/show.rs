@@ -14,7 +14,7 @@ use crate::{
     query_list, Nil,
 };
 
-use super::{Client, Endpoint};
+use super::{check_chunk_len, Client, Endpoint, ExtraParams, MAX_IDS, MAX_LIMIT};
 
 impl Endpoint for ShowEndpoint {}
 impl Endpoint for ShowsEndpoint {}
@@ -24,10 +24,21 @@ impl Endpoint for EpisodeEndpoint {}
 impl Endpoint for EpisodesEndpoint {}
 impl Endpoint for SavedEpisodesEndpoint {}
 
+impl_extra_params!(
+    ShowEndpoint,
+    ShowsEndpoint,
+    ShowEpisodesEndpoint,
+    SavedShowsEndpoint,
+    EpisodeEndpoint,
+    EpisodesEndpoint,
+    SavedEpisodesEndpoint
+);
+
 pub fn show(id: impl Into<String>) -> ShowEndpoint {
     ShowEndpoint {
         id: id.into(),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -35,6 +46,7 @@ pub fn shows<T: AsRef<str>>(ids: &[T]) -> ShowsEndpoint {
     ShowsEndpoint {
         ids: query_list(ids),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -80,6 +92,7 @@ pub fn episode(id: impl Into<String>) -> EpisodeEndpoint {
     EpisodeEndpoint {
         id: id.into(),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -87,6 +100,7 @@ pub fn episodes<T: AsRef<str>>(ids: &[T]) -> EpisodesEndpoint {
     EpisodesEndpoint {
         ids: query_list(ids),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -129,6 +143,8 @@ pub struct ShowEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ShowEndpoint {
@@ -139,7 +155,9 @@ impl ShowEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Show> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Show> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get(format!("/shows/{}", self.id), self).await
     }
 }
@@ -148,6 +166,8 @@ impl ShowEndpoint {
 pub struct ShowsEndpoint {
     pub(crate) ids: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ShowsEndpoint {
@@ -159,12 +179,36 @@ impl ShowsEndpoint {
 
     // This doesn't flatten the result into a Vec<SimplifiedShow> because the user might want to
     // know that some of the shows they want return null.
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<SimplifiedShow>>> {
-        spotify
-            .get("/shows/".to_owned(), self)
-            .await
-            .map(|s: Shows| s.shows)
+    /// Send the request, returning the shows in the same order as the IDs,
+    /// with `None` for the shows that couldn't be found.
+    ///
+    /// Any number of IDs can be used, as they are requested in chunks of 50
+    /// (the maximum Spotify allows per request).
+    pub async fn get(
+        mut self,
+        spotify: &Client<impl AuthFlow>,
+    ) -> Result<Vec<Option<SimplifiedShow>>> {
+        // Splitting no IDs would still give an (empty) ID to request.
+        if self.ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.market = spotify.market_or_default(self.market);
+        let ids: Vec<&str> = self.ids.split(',').collect();
+        let mut shows = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(MAX_IDS) {
+            let endpoint = Self {
+                ids: chunk.join(","),
+                market: self.market.clone(),
+                extra_params: self.extra_params.clone(),
+            };
+
+            let chunk_shows: Shows = spotify.get("/shows/".to_owned(), endpoint).await?;
+            shows.extend(check_chunk_len(chunk_shows.shows, chunk.len(), "shows")?);
+        }
+
+        Ok(shows)
     }
 }
 
@@ -175,6 +219,8 @@ pub struct ShowEpisodesEndpoint {
     pub(crate) market: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl ShowEpisodesEndpoint {
@@ -186,7 +232,7 @@ impl ShowEpisodesEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -197,7 +243,9 @@ impl ShowEpisodesEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedEpisode>> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Page<SimplifiedEpisode>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify
             .get(format!("/shows/{}/episodes", self.show_id), self)
             .await
@@ -208,12 +256,14 @@ impl ShowEpisodesEndpoint {
 pub struct SavedShowsEndpoint {
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SavedShowsEndpoint {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -237,6 +287,8 @@ pub struct EpisodeEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl EpisodeEndpoint {
@@ -247,7 +299,9 @@ impl EpisodeEndpoint {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Episode> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Episode> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get(format!("/episodes/{}", self.id), self).await
     }
 }
@@ -256,6 +310,8 @@ impl EpisodeEndpoint {
 pub struct EpisodesEndpoint {
     pub(crate) ids: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl EpisodesEndpoint {
@@ -265,12 +321,38 @@ impl EpisodesEndpoint {
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Episode>>> {
-        spotify
-            .get("/episodes/".to_owned(), self)
-            .await
-            .map(|e: Episodes| e.episodes)
+    /// Send the request, returning the episodes in the same order as the IDs,
+    /// with `None` for the episodes that couldn't be found or aren't available
+    /// in the requested market.
+    ///
+    /// Any number of IDs can be used, as they are requested in chunks of 50
+    /// (the maximum Spotify allows per request).
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Episode>>> {
+        // Splitting no IDs would still give an (empty) ID to request.
+        if self.ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.market = spotify.market_or_default(self.market);
+        let ids: Vec<&str> = self.ids.split(',').collect();
+        let mut episodes = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(MAX_IDS) {
+            let endpoint = Self {
+                ids: chunk.join(","),
+                market: self.market.clone(),
+                extra_params: self.extra_params.clone(),
+            };
+
+            let chunk_episodes: Episodes = spotify.get("/episodes/".to_owned(), endpoint).await?;
+            episodes.extend(check_chunk_len(
+                chunk_episodes.episodes,
+                chunk.len(),
+                "episodes",
+            )?);
+        }
+
+        Ok(episodes)
     }
 }
 
@@ -279,6 +361,8 @@ pub struct SavedEpisodesEndpoint {
     pub(crate) market: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SavedEpisodesEndpoint {
@@ -290,7 +374,7 @@ impl SavedEpisodesEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -302,9 +386,139 @@ impl SavedEpisodesEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(
-        self,
+        mut self,
         spotify: &Client<impl AuthFlow + Authorised>,
     ) -> Result<Page<SavedEpisode>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get("/me/episodes".to_owned(), self).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{client::mock::MockTransport, error::Error};
+
+    fn episode_json(id: &str) -> Value {
+        json!({
+            "audio_preview_url": null,
+            "description": "",
+            "html_description": "",
+            "duration_ms": 1000,
+            "explicit": false,
+            "external_urls": { "spotify": format!("https://open.spotify.com/episode/{id}") },
+            "href": format!("https://api.spotify.com/v1/episodes/{id}"),
+            "id": id,
+            "images": [],
+            "is_externally_hosted": false,
+            "is_playable": true,
+            "languages": ["en"],
+            "name": id,
+            "release_date": "2024-01-01",
+            "release_date_precision": "day",
+            "type": "episode",
+            "uri": format!("spotify:episode:{id}"),
+            "show": {
+                "available_markets": [],
+                "copyrights": [],
+                "description": "",
+                "html_description": "",
+                "explicit": false,
+                "external_urls": { "spotify": "https://open.spotify.com/show/show" },
+                "href": "https://api.spotify.com/v1/shows/show",
+                "id": "show",
+                "images": [],
+                "is_externally_hosted": false,
+                "languages": ["en"],
+                "media_type": "audio",
+                "name": "Show",
+                "publisher": "Publisher",
+                "type": "show",
+                "uri": "spotify:show:show",
+                "total_episodes": 60
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn shows_and_episodes_without_ids_send_no_request() {
+        let transport = MockTransport::new(|_| (400, String::new()));
+        let spotify = transport.client();
+
+        let shows = shows::<&str>(&[]).get(&spotify).await.unwrap();
+        let episodes = episodes::<&str>(&[]).get(&spotify).await.unwrap();
+
+        assert!(shows.is_empty());
+        assert!(episodes.is_empty());
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn episodes_keep_unavailable_ids_aligned_across_chunks() {
+        let ids: Vec<String> = (0..60).map(|i| format!("episode{i}")).collect();
+        let unavailable = ["episode3", "episode49", "episode50", "episode59"];
+
+        let transport = MockTransport::new(move |req| {
+            let ids = req.query("ids").unwrap();
+            let episodes: Vec<Value> = ids
+                .split(',')
+                .map(|id| {
+                    if unavailable.contains(&id) {
+                        Value::Null
+                    } else {
+                        episode_json(id)
+                    }
+                })
+                .collect();
+
+            (200, json!({ "episodes": episodes }).to_string())
+        });
+        let spotify = transport.client();
+
+        let episodes = episodes(&ids).market("GB").get(&spotify).await.unwrap();
+
+        assert_eq!(episodes.len(), ids.len());
+
+        for (id, episode) in ids.iter().zip(&episodes) {
+            match episode {
+                Some(episode) => {
+                    assert!(!unavailable.contains(&id.as_str()));
+                    assert_eq!(&episode.id, id);
+                }
+                None => assert!(unavailable.contains(&id.as_str()), "{id} is missing"),
+            }
+        }
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].query("ids").unwrap().split(',').count(), 50);
+        assert_eq!(requests[1].query("ids").unwrap().split(',').count(), 10);
+        assert!(requests
+            .iter()
+            .all(|r| r.query("market").as_deref() == Some("GB")));
+    }
+
+    #[tokio::test]
+    async fn episodes_fail_when_the_response_is_short() {
+        let ids: Vec<String> = (0..60).map(|i| format!("episode{i}")).collect();
+
+        let transport = MockTransport::new(|req| {
+            let ids = req.query("ids").unwrap();
+            // Leave out the last episode, so the results can't be matched to
+            // the IDs.
+            let episodes: Vec<Value> = ids.split(',').skip(1).map(episode_json).collect();
+
+            (200, json!({ "episodes": episodes }).to_string())
+        });
+        let spotify = transport.client();
+
+        let result = episodes(&ids).get(&spotify).await;
+
+        assert!(matches!(result, Err(Error::UnexpectedResponse { .. })));
+        // The rest of the IDs aren't requested after the first short response.
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, fmt::Debug, marker::PhantomData};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    marker::PhantomData,
+};
 
 use serde::{ser::SerializeMap, Serialize};
 use strum::IntoStaticStr;
@@ -6,7 +10,7 @@ use strum::IntoStaticStr;
 use crate::{
     auth::{AuthFlow, Authorised},
     body_list,
-    error::Result,
+    error::{Error, Result},
     model::{
         audio::{AudioAnalysis, AudioFeatures, AudioFeaturesList, Mode},
         recommendation::Recommendations,
@@ -16,12 +20,16 @@ use crate::{
     query_list, Nil,
 };
 
-use super::{Client, Endpoint};
+use super::{check_chunk_len, Client, Endpoint, ExtraParams, MAX_IDS, MAX_LIMIT};
+
+// The maximum limit when getting recommendations.
+const RECOMMENDATIONS_MAX_LIMIT: u32 = 100;
 
 pub fn track(id: impl Into<String>) -> TrackEndpoint {
     TrackEndpoint {
         id: id.into(),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -29,6 +37,7 @@ pub fn tracks<T: AsRef<str>>(ids: &[T]) -> TracksEndpoint {
     TracksEndpoint {
         ids: query_list(ids),
         market: None,
+        ..Default::default()
     }
 }
 
@@ -40,27 +49,124 @@ pub async fn save_tracks<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<Nil> {
-    spotify
+    let res = spotify
         .put("/me/tracks".to_owned(), body_list("ids", ids))
-        .await
+        .await?;
+
+    if let Some(cache) = &spotify.save_state_cache {
+        cache.set(ids.iter().map(|id| (id.as_ref(), true)));
+    }
+
+    Ok(res)
 }
 
 pub async fn remove_saved_tracks<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<Nil> {
-    spotify
+    let res = spotify
         .delete("/me/tracks".to_owned(), body_list("ids", ids))
-        .await
+        .await?;
+
+    if let Some(cache) = &spotify.save_state_cache {
+        cache.set(ids.iter().map(|id| (id.as_ref(), false)));
+    }
+
+    Ok(res)
 }
 
+/// Check if one or more tracks are saved in the current user's library.
+///
+/// Any number of IDs can be used, as they are checked in chunks of 50
+/// (the maximum Spotify allows per request).
+///
+/// If the client has a [save state cache](crate::client::Client::with_save_state_cache),
+/// only the tracks that aren't cached are checked.
 pub async fn check_saved_tracks<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow + Authorised>,
 ) -> Result<Vec<bool>> {
-    spotify
-        .get("/me/tracks/contains".to_owned(), [("ids", query_list(ids))])
-        .await
+    let Some(cache) = &spotify.save_state_cache else {
+        let ids: Vec<&str> = ids.iter().map(AsRef::as_ref).collect();
+        return fetch_saved_tracks(&ids, spotify).await;
+    };
+
+    let cached: Vec<Option<bool>> = ids.iter().map(|id| cache.get(id.as_ref())).collect();
+    let missing: Vec<&str> = ids
+        .iter()
+        .zip(&cached)
+        .filter(|(_, saved)| saved.is_none())
+        .map(|(id, _)| id.as_ref())
+        .collect();
+
+    let fetched = fetch_saved_tracks(&missing, spotify).await?;
+    cache.set(missing.iter().copied().zip(fetched.iter().copied()));
+
+    // There's exactly one fetched state for each track that wasn't cached.
+    let mut fetched = fetched.into_iter();
+    Ok(cached
+        .into_iter()
+        .map(|saved| saved.or_else(|| fetched.next()).unwrap_or_default())
+        .collect())
+}
+
+// Check if the given tracks are saved, in chunks of 50, failing if Spotify
+// doesn't return exactly one result for each track.
+async fn fetch_saved_tracks(
+    ids: &[&str],
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<Vec<bool>> {
+    let mut saved = Vec::with_capacity(ids.len());
+
+    for chunk in ids.chunks(MAX_IDS) {
+        let chunk_saved: Vec<bool> = spotify
+            .get(
+                "/me/tracks/contains".to_owned(),
+                [("ids", query_list(chunk))],
+            )
+            .await?;
+
+        if chunk_saved.len() != chunk.len() {
+            return Err(Error::UnexpectedResponse {
+                description: format!(
+                    "Checked {} saved tracks, but Spotify returned {} results.",
+                    chunk.len(),
+                    chunk_saved.len()
+                ),
+            });
+        }
+
+        saved.extend(chunk_saved);
+    }
+
+    Ok(saved)
+}
+
+/// Check if one or more tracks are saved in the current user's library,
+/// returning a map of each track ID to whether or not it's saved.
+///
+/// Duplicate IDs are only checked once, and any number of IDs can be given,
+/// as they are checked in chunks of 50 (the maximum Spotify allows per request).
+pub async fn check_saved_tracks_map<T: AsRef<str>>(
+    ids: &[T],
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<HashMap<String, bool>> {
+    // Keep the first occurrence of each ID, preserving the input order.
+    let mut seen = HashSet::with_capacity(ids.len());
+    let unique_ids: Vec<&str> = ids
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|id| seen.insert(*id))
+        .collect();
+
+    let mut saved = HashMap::with_capacity(unique_ids.len());
+
+    for chunk in unique_ids.chunks(MAX_IDS) {
+        let results = check_saved_tracks(chunk, spotify).await?;
+        saved.extend(chunk.iter().map(|id| id.to_string()).zip(results));
+    }
+
+    Ok(saved)
 }
 
 /// **Note:** This endpoint has been deprecated by Spotify. It continues to work for
@@ -122,6 +228,7 @@ pub fn recommendations<S: SeedType, T: AsRef<str>>(seed: Seed<T, S>) -> Recommen
         limit: None,
         market: None,
         features: None,
+        extra_params: ExtraParams::new(),
         marker: std::marker::PhantomData,
     }
 }
@@ -131,6 +238,9 @@ impl Endpoint for TracksEndpoint {}
 impl Endpoint for SavedTracksEndpoint {}
 impl<S: SeedType> Endpoint for RecommendationsEndpoint<S> {}
 
+impl_extra_params!(TrackEndpoint, TracksEndpoint, SavedTracksEndpoint);
+impl_extra_params!([S: SeedType] RecommendationsEndpoint<S>);
+
 pub trait SeedType: Debug {}
 impl SeedType for SeedArtists {}
 impl SeedType for SeedGenres {}
@@ -413,6 +523,8 @@ pub struct TrackEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl TrackEndpoint {
@@ -422,8 +534,25 @@ impl TrackEndpoint {
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Track> {
+    /// Sets the market to the one of the current user (the country associated
+    /// with the access token), so the track is relinked to a version that's
+    /// playable for them, if needed. The originally requested track is then
+    /// found in [`Track::linked_from`].
+    ///
+    /// This requires a user access token (i.e. not the Client Credentials flow).
+    pub fn market_from_token(self) -> Self {
+        self.market("from_token")
+    }
+
+    /// Send the request.
+    ///
+    /// If no track has the ID, this fails with an [`Error::Spotify`](crate::Error::Spotify)
+    /// error, for which [`is_not_found`](crate::Error::is_not_found) returns `true`,
+    /// rather than a deserialization error. If the ID is malformed, the status
+    /// code of the error is `400` instead.
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Track> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get(format!("/tracks/{}", self.id), self).await
     }
 }
@@ -431,6 +560,8 @@ impl TrackEndpoint {
 pub struct TracksEndpoint {
     pub(crate) ids: String,
     pub(crate) market: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl TracksEndpoint {
@@ -440,12 +571,33 @@ impl TracksEndpoint {
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Track>> {
-        spotify
-            .get("/tracks".to_owned(), self)
-            .await
-            .map(|t: Tracks| t.tracks)
+    /// Send the request, returning the tracks in the same order as the IDs,
+    /// with `None` for the tracks that couldn't be found.
+    ///
+    /// Any number of IDs can be used, as they are requested in chunks of 50
+    /// (the maximum Spotify allows per request).
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Vec<Option<Track>>> {
+        // Splitting no IDs would still give an (empty) ID to request.
+        if self.ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.market = spotify.market_or_default(self.market);
+        let ids: Vec<&str> = self.ids.split(',').collect();
+        let mut tracks = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(MAX_IDS) {
+            let endpoint = Self {
+                ids: chunk.join(","),
+                market: self.market.clone(),
+                extra_params: self.extra_params.clone(),
+            };
+
+            let chunk_tracks: Tracks = spotify.get("/tracks".to_owned(), endpoint).await?;
+            tracks.extend(check_chunk_len(chunk_tracks.tracks, chunk.len(), "tracks")?);
+        }
+
+        Ok(tracks)
     }
 }
 
@@ -454,6 +606,8 @@ pub struct SavedTracksEndpoint {
     pub(crate) market: Option<String>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl SavedTracksEndpoint {
@@ -465,7 +619,7 @@ impl SavedTracksEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -477,9 +631,11 @@ impl SavedTracksEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(
-        self,
+        mut self,
         spotify: &Client<impl AuthFlow + Authorised>,
     ) -> Result<Page<SavedTrack>> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get("/me/tracks".to_owned(), self).await
     }
 }
@@ -493,6 +649,8 @@ pub struct RecommendationsEndpoint<S: SeedType> {
     pub(crate) market: Option<String>,
     #[serde(flatten)]
     pub(crate) features: Option<Features>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
     #[serde(skip)]
     pub(crate) marker: PhantomData<S>,
 }
@@ -540,9 +698,11 @@ impl RecommendationsEndpoint<SeedTracks> {
 }
 
 impl<S: SeedType> RecommendationsEndpoint<S> {
-    #[doc = include_str!("../docs/limit.md")]
+    /// The target size of the list of recommended tracks. Defaults to `20`.
+    ///
+    /// It's clamped between `1` - `100`.
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, RECOMMENDATIONS_MAX_LIMIT));
         self
     }
 
@@ -560,7 +720,111 @@ impl<S: SeedType> RecommendationsEndpoint<S> {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self, spotify: &Client<impl AuthFlow>) -> Result<Recommendations> {
+    pub async fn get(mut self, spotify: &Client<impl AuthFlow>) -> Result<Recommendations> {
+        self.market = spotify.market_or_default(self.market);
+
         spotify.get("/recommendations".to_owned(), self).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::mock::MockTransport;
+
+    // Every track whose ID ends in an even digit is saved.
+    fn is_saved(id: &str) -> bool {
+        id.ends_with(['0', '2', '4', '6', '8'])
+    }
+
+    #[tokio::test]
+    async fn track_from_token_market_is_not_found_for_unknown_id() {
+        let transport = MockTransport::new(|req| {
+            let (status, message) = if req.path_and_query.starts_with("/v1/tracks/unknown") {
+                (404, "Resource not found")
+            } else {
+                (400, "Invalid base62 id")
+            };
+            let error = serde_json::json!({ "error": { "status": status, "message": message } });
+
+            (status, error.to_string())
+        });
+        let spotify = transport.client();
+
+        let err = track("unknown")
+            .market_from_token()
+            .get(&spotify)
+            .await
+            .unwrap_err();
+
+        assert!(err.is_not_found(), "{err:?}");
+        assert_eq!(
+            transport.requests()[0].query("market").as_deref(),
+            Some("from_token")
+        );
+
+        let err = track("malformed!").get(&spotify).await.unwrap_err();
+
+        assert_eq!(err.status_code(), Some(400));
+        assert!(!err.is_not_found());
+    }
+
+    #[tokio::test]
+    async fn tracks_without_ids_sends_no_request() {
+        let transport = MockTransport::new(|_| (400, String::new()));
+        let spotify = transport.client();
+
+        let tracks = tracks::<&str>(&[]).get(&spotify).await.unwrap();
+
+        assert!(tracks.is_empty());
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn check_saved_tracks_chunks_uncached_ids() {
+        let transport = MockTransport::new(|req| {
+            let ids = req.query("ids").unwrap();
+            let saved: Vec<bool> = ids.split(',').map(is_saved).collect();
+
+            (200, serde_json::to_string(&saved).unwrap())
+        });
+        let spotify = transport
+            .client()
+            .with_save_state_cache(Duration::from_secs(60));
+
+        let ids: Vec<String> = (0..120).map(|i| format!("track{i}")).collect();
+
+        // Cache the first 10 tracks.
+        check_saved_tracks(&ids[..10], &spotify).await.unwrap();
+
+        let saved = check_saved_tracks(&ids, &spotify).await.unwrap();
+        let expected: Vec<bool> = ids.iter().map(|id| is_saved(id)).collect();
+        assert_eq!(saved, expected);
+
+        let requests = transport.requests();
+        let chunk_sizes: Vec<usize> = requests
+            .iter()
+            .map(|r| r.query("ids").unwrap().split(',').count())
+            .collect();
+        assert_eq!(chunk_sizes, [10, 50, 50, 10]);
+    }
+
+    #[tokio::test]
+    async fn check_saved_tracks_fails_on_short_response() {
+        let transport = MockTransport::new(|_| (200, "[true]".to_owned()));
+        let spotify = transport
+            .client()
+            .with_save_state_cache(Duration::from_secs(60));
+
+        let result = check_saved_tracks(&["track1", "track2"], &spotify).await;
+
+        assert!(matches!(result, Err(Error::UnexpectedResponse { .. })));
+        // Nothing is cached, so the tracks are checked again next time.
+        check_saved_tracks(&["track1", "track2"], &spotify)
+            .await
+            .unwrap_err();
+        assert_eq!(transport.requests().len(), 2);
+    }
+}
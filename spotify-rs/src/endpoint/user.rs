@@ -1,10 +1,13 @@
+use std::collections::{BTreeMap, HashMap};
+
+use futures_util::future::try_join_all;
 use serde::Serialize;
 use serde_json::json;
 
 use crate::{
     auth::{AuthFlow, Authorised},
     client::Body,
-    error::Result,
+    error::{Error, Result},
     model::{
         artist::{Artist, PagedArtists},
         track::Track,
@@ -14,7 +17,16 @@ use crate::{
     query_list, Nil,
 };
 
-use super::{Client, Endpoint, EndpointPrivate};
+use super::{
+    album::check_saved_albums,
+    audiobook::check_saved_audiobooks,
+    show::{check_saved_episodes, check_saved_shows},
+    track::check_saved_tracks,
+    with_extra_params, Client, Endpoint, EndpointPrivate, ExtraParams, MAX_IDS, MAX_LIMIT,
+};
+
+// The maximum amount of user IDs that can be checked for following a playlist.
+const MAX_FOLLOW_CHECK_IDS: usize = 5;
 
 pub async fn get_current_user_profile(
     spotify: &Client<impl AuthFlow + Authorised>,
@@ -22,14 +34,25 @@ pub async fn get_current_user_profile(
     spotify.get::<(), _>("/me".to_owned(), None).await
 }
 
+/// Get the current user's top artists, calculated over the
+/// [medium term](TimeRange::MediumTerm) unless a different
+/// [time range](UserTopItemsEndpoint::time_range) is set.
 pub fn current_user_top_artists() -> UserTopItemsEndpoint<ArtistsMarker> {
     UserTopItemsEndpoint::default()
 }
 
+/// Get the current user's top tracks, calculated over the
+/// [medium term](TimeRange::MediumTerm) unless a different
+/// [time range](UserTopItemsEndpoint::time_range) is set.
 pub fn current_user_top_tracks() -> UserTopItemsEndpoint<TracksMarker> {
     UserTopItemsEndpoint::default()
 }
 
+/// Get the public profile of a user.
+///
+/// If no user with the given ID exists, this will return an
+/// [`Error::Spotify`](crate::Error::Spotify) with a `404` status, which can be
+/// checked with [`Error::is_not_found`](crate::Error::is_not_found).
 pub async fn get_user(id: impl Into<String>, spotify: &Client<impl AuthFlow>) -> Result<User> {
     spotify
         .get::<(), _>(format!("/users/{}", id.into()), None)
@@ -39,7 +62,7 @@ pub async fn get_user(id: impl Into<String>, spotify: &Client<impl AuthFlow>) ->
 pub fn follow_playlist(id: impl Into<String>) -> FollowPlaylistEndpoint {
     FollowPlaylistEndpoint {
         id: id.into(),
-        public: None,
+        ..Default::default()
     }
 }
 
@@ -100,6 +123,65 @@ pub async fn check_if_user_follows_artists<T: AsRef<str>>(
         .await
 }
 
+/// Check whether several items are saved in the current user's library or
+/// followed by them, e.g. to show a "heart" or "follow" button for each one.
+///
+/// The items are given by their Spotify URIs (e.g. `spotify:track:{id}`), which
+/// are grouped by type, and every type is checked concurrently. Tracks, albums,
+/// shows, episodes and audiobooks are checked for being saved, while artists
+/// and users are checked for being followed.
+///
+/// Returns a map of each URI to its state. URIs of other types (such as
+/// playlists) or that are invalid aren't included.
+pub async fn get_library_state<T: AsRef<str>>(
+    uris: &[T],
+    spotify: &Client<impl AuthFlow + Authorised>,
+) -> Result<HashMap<String, bool>> {
+    // The URIs and IDs of the items, grouped by type.
+    let mut groups: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+
+    for uri in uris {
+        let uri = uri.as_ref();
+        let mut parts = uri.splitn(3, ':');
+
+        if let (Some("spotify"), Some(item_type), Some(id)) =
+            (parts.next(), parts.next(), parts.next())
+        {
+            groups.entry(item_type).or_default().push((uri, id));
+        }
+    }
+
+    let checks = groups.into_iter().map(|(item_type, items)| async move {
+        let ids: Vec<&str> = items.iter().map(|(_, id)| *id).collect();
+        let mut states = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(MAX_IDS) {
+            let chunk_states = match item_type {
+                "track" => check_saved_tracks(chunk, spotify).await?,
+                "album" => check_saved_albums(chunk, spotify).await?,
+                "show" => check_saved_shows(chunk, spotify).await?,
+                "episode" => check_saved_episodes(chunk, spotify).await?,
+                "audiobook" => check_saved_audiobooks(chunk, spotify).await?,
+                "artist" => check_if_user_follows_artists(chunk, spotify).await?,
+                "user" => check_if_user_follows_users(chunk, spotify).await?,
+                _ => return Ok(vec![]),
+            };
+
+            states.extend(chunk_states);
+        }
+
+        Ok::<_, Error>(
+            items
+                .into_iter()
+                .map(|(uri, _)| uri.to_owned())
+                .zip(states)
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    Ok(try_join_all(checks).await?.into_iter().flatten().collect())
+}
+
 pub async fn follow_users<T: AsRef<str>>(
     ids: &[T],
     spotify: &Client<impl AuthFlow + Authorised>,
@@ -152,6 +234,37 @@ pub async fn check_if_current_user_follow_playlist(
         .await
 }
 
+/// Check if one or more users follow a playlist, returning whether or not
+/// each user follows it, in the same order as the given user IDs.
+///
+/// Up to 5 user IDs can be checked, otherwise an
+/// [`Error::InvalidArgument`](crate::error::Error::InvalidArgument) is returned.
+pub async fn check_users_follow_playlist<T: AsRef<str>>(
+    playlist_id: impl Into<String>,
+    user_ids: &[T],
+    spotify: &Client<impl AuthFlow>,
+) -> Result<Vec<bool>> {
+    if user_ids.len() > MAX_FOLLOW_CHECK_IDS {
+        return Err(Error::InvalidArgument {
+            description: format!(
+                "At most {MAX_FOLLOW_CHECK_IDS} user IDs can be checked, but {} were given.",
+                user_ids.len()
+            ),
+        });
+    }
+
+    if user_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    spotify
+        .get(
+            format!("/playlists/{}/followers/contains", playlist_id.into()),
+            [("ids", query_list(user_ids))],
+        )
+        .await
+}
+
 pub trait ItemType: private::Sealed {}
 impl ItemType for ArtistsMarker {}
 impl ItemType for TracksMarker {}
@@ -172,17 +285,24 @@ impl<I: ItemType> Endpoint for UserTopItemsEndpoint<I> {}
 impl Endpoint for FollowPlaylistEndpoint {}
 impl Endpoint for FollowedArtistsEndpoint {}
 
+impl_extra_params!(FollowPlaylistEndpoint, FollowedArtistsEndpoint);
+impl_extra_params!([I: ItemType] UserTopItemsEndpoint<I>);
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct UserTopItemsEndpoint<ItemType> {
     pub(crate) time_range: Option<TimeRange>,
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
     #[serde(skip)]
     marker: std::marker::PhantomData<ItemType>,
 }
 
 impl<I: ItemType> UserTopItemsEndpoint<I> {
     /// The time frame of the computed affinities.
+    ///
+    /// If not set, Spotify uses [`TimeRange::MediumTerm`].
     pub fn time_range(mut self, time_range: TimeRange) -> Self {
         self.time_range = Some(time_range);
         self
@@ -190,7 +310,7 @@ impl<I: ItemType> UserTopItemsEndpoint<I> {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -221,6 +341,8 @@ pub struct FollowPlaylistEndpoint {
     pub(crate) id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) public: Option<bool>,
+    #[serde(skip)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl FollowPlaylistEndpoint {
@@ -233,9 +355,12 @@ impl FollowPlaylistEndpoint {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self, spotify: &Client<impl AuthFlow + Authorised>) -> Result<Nil> {
-        spotify
-            .put(format!("/playlists/{}/followers", self.id), self.json())
-            .await
+        let endpoint = with_extra_params(
+            format!("/playlists/{}/followers", self.id),
+            &self.extra_params,
+        );
+
+        spotify.put(endpoint, self.json()).await
     }
 }
 
@@ -244,6 +369,8 @@ pub struct FollowedArtistsEndpoint {
     pub(crate) r#type: String,
     pub(crate) after: Option<String>,
     pub(crate) limit: Option<u32>,
+    #[serde(flatten)]
+    pub(crate) extra_params: ExtraParams,
 }
 
 impl FollowedArtistsEndpoint {
@@ -255,7 +382,7 @@ impl FollowedArtistsEndpoint {
 
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.limit = Some(limit);
+        self.limit = Some(limit.clamp(1, MAX_LIMIT));
         self
     }
 
@@ -1,7 +1,11 @@
+use std::path::PathBuf;
+
 use oauth2::basic::BasicErrorResponseType;
 use serde::Deserialize;
 use snafu::prelude::*;
 
+use crate::auth::Scope;
+
 /// A convenience result type that uses [`enum@Error`] by default.
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -22,12 +26,50 @@ pub enum Error {
     ))]
     InvalidStateParameter,
 
+    /// The user denied access to the app, or another error occurred during the
+    /// authorisation.
+    #[snafu(display("The authorisation failed: {error}"))]
+    AuthorisationDenied {
+        /// The reason the authorisation failed, as returned by Spotify (e.g. `access_denied`).
+        error: String,
+    },
+
     /// The access token has expired and refreshing it is not possible in the current authorisation flow.
     RefreshUnavailable,
 
     // There are no remaining pages left, either before or after the current one.
     NoRemainingPages,
 
+    /// There is no item currently playing.
+    NothingPlaying,
+
+    /// The duration of the currently playing item is unknown.
+    UnknownDuration,
+
+    /// The access token doesn't have a scope required by the endpoint.
+    #[snafu(display(
+        "The access token doesn't have the scope required by the endpoint{}.",
+        required.as_ref().map(|s| format!(" ({s})")).unwrap_or_default()
+    ))]
+    InsufficientScope {
+        /// The required scope, if Spotify mentioned it.
+        required: Option<Scope>,
+    },
+
+    /// There is no active device to control playback on, and no device was
+    /// specified.
+    #[snafu(display("There is no active device to control playback on."))]
+    NoActiveDevice,
+
+    /// The user doesn't have a Spotify Premium subscription, which is required
+    /// to control playback.
+    #[snafu(display("Spotify Premium is required to control playback."))]
+    PremiumRequired,
+
+    /// The device doesn't allow its volume to be controlled.
+    #[snafu(display("The device doesn't allow its volume to be controlled."))]
+    VolumeNotSupported,
+
     /// An error that indicates that an internal error occurred and the
     /// client had no PKCE verifier when authenticating.
     ///
@@ -40,6 +82,13 @@ pub enum Error {
     /// The returned data is not valid valid UTF-8.
     InvalidResponse,
 
+    /// The response is valid, but doesn't match the request (e.g. it has fewer
+    /// results than the IDs that were requested).
+    #[snafu(display("{description}"))]
+    UnexpectedResponse {
+        description: String,
+    },
+
     /// An error returned by Spotify.
     #[snafu(display("Error returned by the Spotify API: {status} {description}"))]
     Spotify {
@@ -54,6 +103,29 @@ pub enum Error {
         source: OauthError,
     },
 
+    /// An argument passed to an endpoint is invalid (e.g. too many IDs).
+    #[snafu(display("{description}"))]
+    InvalidArgument {
+        description: String,
+    },
+
+    /// An environment variable required to create a client isn't set (or
+    /// isn't valid Unicode).
+    #[snafu(display("The environment variable {name} is not set."))]
+    MissingEnvVar {
+        /// The name of the environment variable.
+        name: String,
+    },
+
+    /// A file couldn't be read, e.g. when uploading a playlist cover from a
+    /// path.
+    #[snafu(display("Failed to read the file {}.", path.display()))]
+    Io {
+        source: std::io::Error,
+        /// The path of the file.
+        path: PathBuf,
+    },
+
     /// An error related to parsing items.
     #[snafu(display("{description}"))]
     Parse {
@@ -75,6 +147,87 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Get the HTTP status code of the error, if it resulted from an HTTP
+    /// response.
+    ///
+    /// Returns `None` for errors unrelated to an HTTP response, such as
+    /// deserialization errors, as well as for the errors that can also be
+    /// raised without a response, like [`NoActiveDevice`](Self::NoActiveDevice),
+    /// [`PremiumRequired`](Self::PremiumRequired),
+    /// [`VolumeNotSupported`](Self::VolumeNotSupported) and
+    /// [`InsufficientScope`](Self::InsufficientScope), which should be matched
+    /// on directly instead.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::Spotify { status, .. } => Some(*status),
+            Self::Http { source } => source.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Whether or not the error is a `404 Not Found` error, e.g. from requesting
+    /// a user or item that doesn't exist.
+    pub fn is_not_found(&self) -> bool {
+        self.status_code() == Some(404)
+    }
+
+    /// Get the kind of network error, if this is an [`Http`](Self::Http) error,
+    /// e.g. to only retry requests that timed out.
+    pub fn network_error_kind(&self) -> Option<NetworkErrorKind> {
+        let Self::Http { source } = self else {
+            return None;
+        };
+
+        let kind = if source.is_timeout() {
+            NetworkErrorKind::Timeout
+        } else if source.is_connect() {
+            NetworkErrorKind::Connect
+        } else if source.is_redirect() {
+            NetworkErrorKind::Redirect
+        } else if source.is_status() {
+            NetworkErrorKind::Status
+        } else if source.is_body() {
+            NetworkErrorKind::Body
+        } else if source.is_decode() {
+            NetworkErrorKind::Decode
+        } else if source.is_builder() {
+            NetworkErrorKind::Builder
+        } else if source.is_request() {
+            NetworkErrorKind::Request
+        } else {
+            NetworkErrorKind::Other
+        };
+
+        Some(kind)
+    }
+}
+
+/// The kind of an [`Error::Http`], as reported by the underlying HTTP client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NetworkErrorKind {
+    /// The request timed out.
+    Timeout,
+    /// The connection couldn't be established (e.g. the DNS lookup failed, the
+    /// connection was refused, or the TLS handshake failed).
+    Connect,
+    /// The request was redirected too many times.
+    Redirect,
+    /// The response had an error status code.
+    Status,
+    /// The request or response body couldn't be read or written.
+    Body,
+    /// The response body couldn't be decoded.
+    Decode,
+    /// The request couldn't be built (e.g. because of an invalid URL).
+    Builder,
+    /// Sending the request failed for another reason.
+    Request,
+    /// Any other kind of error.
+    Other,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct SpotifyError {
     error: Details,
@@ -84,6 +237,8 @@ pub(crate) struct SpotifyError {
 struct Details {
     status: u16,
     message: String,
+    // Included by the player endpoints, e.g. `NO_ACTIVE_DEVICE`.
+    reason: Option<String>,
 }
 
 // Error encountered when requesting an OAuth2 access token.
@@ -109,9 +264,73 @@ impl From<reqwest::Error> for Error {
 // Enables the use of the `?` operator.
 impl From<SpotifyError> for Error {
     fn from(value: SpotifyError) -> Self {
+        match value.error.reason.as_deref() {
+            Some("NO_ACTIVE_DEVICE") => return Self::NoActiveDevice,
+            Some("PREMIUM_REQUIRED") => return Self::PremiumRequired,
+            Some("VOLUME_CONTROL_DISALLOW") => return Self::VolumeNotSupported,
+            _ => {}
+        }
+
+        if value.error.status == 403
+            && value
+                .error
+                .message
+                .to_lowercase()
+                .contains("insufficient client scope")
+        {
+            return Self::InsufficientScope {
+                required: required_scope(&value.error.message),
+            };
+        }
+
         Self::Spotify {
             status: value.error.status,
             description: value.error.message,
         }
     }
 }
+
+// Find a known scope mentioned in an error message, if any.
+fn required_scope(message: &str) -> Option<Scope> {
+    message
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .map(Scope::from)
+        .find(|scope| !matches!(scope, Scope::Other(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spotify_error(status: u16, message: &str, reason: Option<&str>) -> Error {
+        SpotifyError {
+            error: Details {
+                status,
+                message: message.to_owned(),
+                reason: reason.map(str::to_owned),
+            },
+        }
+        .into()
+    }
+
+    #[test]
+    fn status_code_is_the_one_returned_by_spotify() {
+        let error = spotify_error(404, "Resource not found", None);
+
+        assert_eq!(error.status_code(), Some(404));
+        assert!(error.is_not_found());
+    }
+
+    #[test]
+    fn local_errors_have_no_status_code() {
+        let error = Error::NoActiveDevice;
+
+        assert_eq!(error.status_code(), None);
+        assert!(!error.is_not_found());
+
+        let error = spotify_error(404, "Player command failed", Some("NO_ACTIVE_DEVICE"));
+
+        assert!(matches!(error, Error::NoActiveDevice));
+        assert!(!error.is_not_found());
+    }
+}
@@ -33,9 +33,9 @@
 //!
 //! The auth flow you should use depends on the use case:
 //! - the authorisation code flow is recommended for long-running applications
-//!     where you can safely store the client secret (e.g. web and mobile apps)
+//!   where you can safely store the client secret (e.g. web and mobile apps)
 //! - the authorisation code with PKCE flow is recommended for long-running applications
-//!     where you *can't* safely store the client secret (e.g. desktop apps and single page web apps)
+//!   where you *can't* safely store the client secret (e.g. desktop apps and single page web apps)
 //! - the client credentials flow doesn't include authorisation, thus letting you only access public information
 //!
 //! Below is an example for each auth flow:
@@ -87,7 +87,7 @@
 //!         .market("GB")
 //!         .get(&spotify)
 //!         .await?;
-//!     println!("The popularity of the album is {}", album_gb.popularity);
+//!     println!("The popularity of the album is {:?}", album_gb.popularity);
 //!
 //!     // This gets 5 playlists of the user that authorised the app
 //!     // (it requires the playlist-read-private scope).
@@ -137,7 +137,7 @@
 //!         .market("GB")
 //!         .get(&spotify)
 //!         .await?;
-//!     println!("The popularity of the album is {}", album_gb.popularity);
+//!     println!("The popularity of the album is {:?}", album_gb.popularity);
 //!
 //!     Ok(())
 //! }
@@ -154,11 +154,13 @@
 //! *will be acquired in order to change the token.*
 //!
 //! If you disable this feature, you'll have to refresh the token yourself using [`request_refresh_token`].
+//! To only refresh it if it has expired, use [`ensure_valid_token`].
 //!
 //! [`Token`]: auth::Token
 //! [`AuthCodeFlow`]: auth::AuthCodeFlow
 //! [`AuthCodePkceFlow`]: auth::AuthCodePkceFlow
 //! [`request_refresh_token`]: client::Client::request_refresh_token()
+//! [`ensure_valid_token`]: client::Client::ensure_valid_token()
 //! [`authenticate`]: client::Client::authenticate()
 
 mod auth;
@@ -171,11 +173,11 @@ mod error;
 pub mod model;
 
 use client::Body;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, de::DeserializeOwned};
 
-pub use auth::{AuthCodePkceFlow, ClientCredsFlow, Token, Unauthenticated};
+pub use auth::{AuthCodePkceFlow, ClientCredsFlow, Scope, Scopes, Token, Unauthenticated};
 pub use client::{AuthCodeClient, AuthCodePkceClient, ClientCredsClient};
-pub use error::{Error, Result as SpotifyResult};
+pub use error::{Error, NetworkErrorKind, Result as SpotifyResult};
 pub use oauth2::RedirectUrl;
 
 #[doc(hidden)]
@@ -186,22 +188,25 @@ pub use endpoint::{
     },
     artist::{artist_albums, artist_top_tracks, get_artist, get_artists, get_related_artists},
     audiobook::{
-        audiobook, audiobook_chapters, audiobooks, chapter, chapters, check_saved_audiobooks,
-        remove_saved_audiobooks, save_audiobooks, saved_audiobooks,
+        audiobook, audiobook_chapters, audiobooks, audiobooks_available, chapter, chapters,
+        check_saved_audiobooks, remove_saved_audiobooks, save_audiobooks, saved_audiobooks,
     },
     category::{browse_categories, browse_category},
     genres::get_genre_seeds,
     markets::get_available_markets,
     player::{
-        add_item_to_queue, get_available_devices, get_currently_playing_track, get_playback_state,
-        get_user_queue, pause_playback, recently_played_tracks, seek_to_position,
+        add_item_to_queue, add_items_to_queue, get_active_device, get_available_devices,
+        get_currently_playing_track, get_playback_state, get_user_queue, pause_playback,
+        recently_played_tracks, resume_episode, seek_to_fraction, seek_to_position,
         set_playback_volume, set_repeat_mode, skip_to_next, skip_to_previous, start_playback,
         toggle_playback_shuffle, transfer_playback,
     },
     playlist::{
-        add_items_to_playlist, add_playlist_image, category_playlists, change_playlist_details,
-        create_playlist, current_user_playlists, featured_playlists, get_playlist_image, playlist,
-        playlist_items, remove_playlist_items, update_playlist_items, user_playlists,
+        add_items_to_playlist, add_items_to_playlist_all, add_playlist_image,
+        add_playlist_image_from_path, category_playlists, change_playlist_details, create_playlist,
+        current_user_playlists, export_playlist, featured_playlists, get_playlist_image,
+        get_playlist_snapshot_id, get_playlists, playlist, playlist_items, remove_playlist_items,
+        update_playlist_items, user_playlists,
     },
     search::search,
     show::{
@@ -210,15 +215,16 @@ pub use endpoint::{
         show_episodes, shows,
     },
     track::{
-        check_saved_tracks, get_track_audio_analysis, get_track_audio_features,
-        get_tracks_audio_features, recommendations, remove_saved_tracks, save_tracks, saved_tracks,
-        track, tracks,
+        check_saved_tracks, check_saved_tracks_map, get_track_audio_analysis,
+        get_track_audio_features, get_tracks_audio_features, recommendations, remove_saved_tracks,
+        save_tracks, saved_tracks, track, tracks,
     },
     user::{
         check_if_current_user_follow_playlist, check_if_user_follows_artists,
-        check_if_user_follows_users, current_user_top_artists, current_user_top_tracks,
-        follow_artists, follow_playlist, follow_users, followed_artists, get_current_user_profile,
-        get_user, unfollow_artists, unfollow_playlist, unfollow_users,
+        check_if_user_follows_users, check_users_follow_playlist, current_user_top_artists,
+        current_user_top_tracks, follow_artists, follow_playlist, follow_users, followed_artists,
+        get_current_user_profile, get_library_state, get_user, unfollow_artists, unfollow_playlist,
+        unfollow_users,
     },
 };
 
@@ -250,3 +256,27 @@ impl<'de> Deserialize<'de> for Nil {
         Ok(Nil)
     }
 }
+
+/// A deserialized response, along with the raw JSON it was deserialized from,
+/// which is useful for debugging when a response doesn't match the model.
+///
+/// Get one with [`Client::get_with_raw`](client::Client::get_with_raw).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Raw<T> {
+    /// The deserialized response.
+    pub value: T,
+    /// The raw JSON of the response.
+    pub raw: serde_json::Value,
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Raw<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = serde_json::Value::deserialize(deserializer)?;
+        let value = T::deserialize(&raw).map_err(serde::de::Error::custom)?;
+
+        Ok(Self { value, raw })
+    }
+}
@@ -1,12 +1,13 @@
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 
 use crate::{
     Error, Token,
     auth::AuthFlow,
-    client::{self, Client},
+    client::Client,
     endpoint::Endpoint,
     error::Result,
 };
+use futures_util::{Stream, StreamExt, stream};
 use serde::{Deserialize, Deserializer, de::DeserializeOwned};
 
 pub mod album;
@@ -23,7 +24,11 @@ pub mod show;
 pub mod track;
 pub mod user;
 
+const WEB_PLAYER_URL: &str = "https://open.spotify.com";
 const PAGE_MAX_LIMIT: u32 = 50;
+// The highest limit a playlist's items can be requested with, the only
+// paginated endpoint that accepts more than `PAGE_MAX_LIMIT`.
+const PLAYLIST_ITEMS_MAX_LIMIT: u32 = 100;
 const PAGINATION_INTERVAL: Duration = Duration::from_millis(100);
 
 /// This represents a page of items, which is a segment of data returned by the
@@ -34,6 +39,7 @@ const PAGINATION_INTERVAL: Duration = Duration::from_millis(100);
 /// [next](Self::get_next) or [previous](Self::get_previous) page, or
 /// the [remaining](Self::get_remaining) or [all](Self::get_all) items.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
 pub struct Page<T: Clone> {
     /// The URL to the API endpoint returning this page.
     pub href: String,
@@ -51,15 +57,76 @@ pub struct Page<T: Clone> {
     pub total: u32,
     /// A list of the items, which includes `null` values.
     /// To get only the `Some` values, use [`filtered_items`](Self::filtered_items).
+    ///
+    /// Items that fail to deserialize are also `None`, rather than failing
+    /// the whole page.
+    #[serde(deserialize_with = "lenient_items")]
     pub items: Vec<Option<T>>,
 }
 
+impl<T: Clone + Explicit> Page<T> {
+    /// Get a list of only the items that aren't explicit.
+    pub fn filter_explicit(&self) -> Vec<T> {
+        self.items
+            .iter()
+            .flatten()
+            .filter(|item| !item.is_explicit())
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T: Clone + Playable> Page<T> {
+    /// Get a list of only the items that are playable.
+    ///
+    /// Items whose playability is unknown (see [`Playable::playable`]) are
+    /// assumed to be playable, and are included.
+    pub fn playable_items(&self) -> Vec<T> {
+        self.items
+            .iter()
+            .flatten()
+            .filter(|item| item.playable() != Some(false))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T: Clone + HasUri> Page<T> {
+    /// Get the Spotify URIs of the page's items, e.g. to start playback or to
+    /// add them to a playlist.
+    ///
+    /// `null` items and items without a usable URI (such as local files) are
+    /// skipped.
+    pub fn uris(&self) -> Vec<String> {
+        self.items
+            .iter()
+            .flatten()
+            .filter_map(|item| item.spotify_uri())
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+}
+
 impl<T: Clone + DeserializeOwned> Page<T> {
     /// Get a list of only the `Some` values from a Page's items.
     pub fn filtered_items(&self) -> Vec<T> {
         self.items.clone().into_iter().flatten().collect()
     }
 
+    /// Estimate how many requests are needed to get the pages after this one,
+    /// if they're requested with the same limit as this page (as with
+    /// [`get_all`](Self::get_all)), e.g. to show progress.
+    ///
+    /// Returns `None` if it can't be estimated, as the page's limit is `0`.
+    pub fn estimated_remaining_requests(&self) -> Option<u32> {
+        if self.limit == 0 {
+            return None;
+        }
+
+        let fetched = self.offset.saturating_add(self.items.len() as u32);
+        Some(self.total.saturating_sub(fetched).div_ceil(self.limit))
+    }
+
     /// Get the next page.
     ///
     /// If there is no next page, this will return an
@@ -69,13 +136,71 @@ impl<T: Clone + DeserializeOwned> Page<T> {
             return Err(Error::NoRemainingPages);
         };
 
-        // Remove `API_URL`from the string, as spotify.get()
+        // Remove the API URL from the string, as spotify.get()
         // (or rather spotify.request) appends it already.
-        let next = next.replace(client::API_URL, "");
+        let next = spotify.relative_url(next);
 
         spotify.get(next, [("limit", self.limit)]).await
     }
 
+    /// Get a stream of this page followed by all the pages after it, which
+    /// are only requested as the stream is polled.
+    ///
+    /// Unlike [`get_remaining`](Self::get_remaining), this keeps the page
+    /// boundaries (and each page's `offset` and `total`), e.g. to process
+    /// one page at a time. The stream ends after the last page, or after the
+    /// first error.
+    pub fn page_stream(
+        self,
+        spotify: &Client<Token, impl AuthFlow>,
+    ) -> impl Stream<Item = Result<Self>> {
+        let cursor = self.without_items();
+
+        stream::once(async { Ok(self) }).chain(stream::unfold(
+            Some(cursor),
+            move |cursor| async move {
+                let cursor = cursor?;
+                cursor.next.as_ref()?;
+
+                tokio::time::sleep(PAGINATION_INTERVAL).await;
+
+                match cursor.get_next(spotify).await {
+                    Ok(page) => {
+                        let cursor = page.without_items();
+                        Some((Ok(page), Some(cursor)))
+                    }
+                    Err(err) => Some((Err(err), None)),
+                }
+            },
+        ))
+    }
+
+    // Get a copy of the page without its items, which is enough to get the
+    // pages around it.
+    fn without_items(&self) -> Self {
+        Self {
+            href: self.href.clone(),
+            limit: self.limit,
+            next: self.next.clone(),
+            offset: self.offset,
+            previous: self.previous.clone(),
+            total: self.total,
+            items: Vec::new(),
+        }
+    }
+
+    // Get the highest limit accepted by the endpoint returning this page,
+    // based on its URL.
+    fn max_limit(&self) -> u32 {
+        let path = self.href.split('?').next().unwrap_or_default();
+
+        if path.contains("/playlists/") && (path.ends_with("/tracks") || path.ends_with("/items")) {
+            PLAYLIST_ITEMS_MAX_LIMIT
+        } else {
+            PAGE_MAX_LIMIT
+        }
+    }
+
     /// Get the previous page.
     ///
     /// If there is no previous page, this will return an
@@ -85,21 +210,64 @@ impl<T: Clone + DeserializeOwned> Page<T> {
             return Err(Error::NoRemainingPages);
         };
 
-        // Remove `API_URL`from the string, as spotify.get()
+        // Remove the API URL from the string, as spotify.get()
         // (or rather spotify.request) appends it already.
-        let previous = previous.replace(client::API_URL, "");
+        let previous = spotify.relative_url(previous);
 
         spotify.get(previous, [("limit", self.limit)]).await
     }
 
     /// Get the items of all the remaining pages - that is, all the pages found
     /// after the current one.
+    ///
+    /// Pagination only stops when there is no next page, so empty pages
+    /// (which Spotify sometimes returns for filtered results) don't end it early.
+    ///
+    /// The pages are requested with a limit of `50` items. To use a different
+    /// limit, see [`get_remaining_with_limit`](Self::get_remaining_with_limit).
     pub async fn get_remaining(
-        mut self,
+        self,
         spotify: &Client<Token, impl AuthFlow>,
     ) -> Result<Vec<Option<T>>> {
+        self.get_remaining_with_limit(PAGE_MAX_LIMIT, spotify).await
+    }
+
+    /// Get the items of all the remaining pages - that is, all the pages found
+    /// after the current one, requesting `limit` items per page.
+    ///
+    /// A playlist's items can be requested with a limit of up to `100`, which
+    /// halves the number of requests. The limit is clamped between `1` and the
+    /// maximum the endpoint accepts: `100` for a playlist's items, `50` otherwise.
+    pub async fn get_remaining_with_limit(
+        self,
+        limit: u32,
+        spotify: &Client<Token, impl AuthFlow>,
+    ) -> Result<Vec<Option<T>>> {
+        match self.remaining_partial(limit, spotify).await {
+            (_, Some(err)) => Err(err),
+            (items, None) => Ok(items),
+        }
+    }
+
+    /// Get the items of all the remaining pages, like
+    /// [`get_remaining`](Self::get_remaining), but if a request fails, return
+    /// the items gathered so far (including this page's) along with the error
+    /// that stopped the pagination, instead of only the error.
+    pub async fn get_remaining_partial(
+        self,
+        spotify: &Client<Token, impl AuthFlow>,
+    ) -> (Vec<Option<T>>, Option<Error>) {
+        self.remaining_partial(PAGE_MAX_LIMIT, spotify).await
+    }
+
+    // Get the items of all the remaining pages, stopping at the first error.
+    async fn remaining_partial(
+        mut self,
+        limit: u32,
+        spotify: &Client<Token, impl AuthFlow>,
+    ) -> (Vec<Option<T>>, Option<Error>) {
         let mut items = std::mem::take(&mut self.items);
-        self.limit = PAGE_MAX_LIMIT;
+        self.limit = limit.clamp(1, self.max_limit());
         let mut page = self;
 
         // Get all the next pages (if any)
@@ -115,7 +283,7 @@ impl<T: Clone + DeserializeOwned> Page<T> {
 
                     Err(err) => match err {
                         Error::NoRemainingPages => break,
-                        _ => return Err(err),
+                        _ => return (items, Some(err)),
                     },
                 };
 
@@ -123,17 +291,31 @@ impl<T: Clone + DeserializeOwned> Page<T> {
             }
         }
 
-        Ok(items)
+        (items, None)
     }
 
     /// Get the items of all of the pages - that is, all the pages found both before and
     /// after the current one.
-    pub async fn get_all(
+    ///
+    /// The pages are requested with the same limit as the current page. To use
+    /// a different limit, see [`get_all_with_limit`](Self::get_all_with_limit).
+    pub async fn get_all(self, spotify: &Client<Token, impl AuthFlow>) -> Result<Vec<Option<T>>> {
+        let limit = self.limit;
+        self.get_all_with_limit(limit, spotify).await
+    }
+
+    /// Get the items of all of the pages - that is, all the pages found both before and
+    /// after the current one, requesting `limit` items per page.
+    ///
+    /// The limit is clamped between `1` and the maximum the endpoint accepts:
+    /// `100` for a playlist's items, `50` otherwise.
+    pub async fn get_all_with_limit(
         mut self,
+        limit: u32,
         spotify: &Client<Token, impl AuthFlow>,
     ) -> Result<Vec<Option<T>>> {
         let mut items = std::mem::take(&mut self.items);
-        self.limit = PAGE_MAX_LIMIT;
+        self.limit = limit.clamp(1, self.max_limit());
 
         // Get all the previous pages (if any)
         if self.previous.is_some() {
@@ -189,13 +371,14 @@ impl<T: Clone + DeserializeOwned> Page<T> {
 ///
 /// It's similar to [`Page`], except that it uses a different approach for
 /// pagination - instead of using a `next` and `previous` field to get another
-/// page, it uses a Unix timestamp (in miliseconds).
+/// page, it uses a Unix timestamp (in milliseconds).
 ///
 /// To get the rest of the data, the `cursors` field (and others), or, preferably,
 /// the [get_before](Self::get_before) and [get_after](Self::get_after) methods can be used.
 // (and possibly in other situations)
 // it happens because some fields are null when they shouldn't be
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
 pub struct CursorPage<T: Clone, E: Endpoint + Default> {
     /// The URL to the API endpoint returning this page.
     pub href: String,
@@ -205,9 +388,16 @@ pub struct CursorPage<T: Clone, E: Endpoint + Default> {
     pub next: Option<String>,
     /// The cursor object used to get the previous/next page.
     pub cursors: Option<Cursor>,
-    /// The amount of returned items.
+    /// The total amount of items, which is omitted by some endpoints.
+    ///
+    /// Use [`has_more`](Self::has_more) to check if there are more items,
+    /// rather than relying on this.
     pub total: Option<u32>,
     /// A list of the items, which includes `null` values.
+    ///
+    /// Items that fail to deserialize are also `None`, rather than failing
+    /// the whole page.
+    #[serde(deserialize_with = "lenient_items")]
     pub items: Vec<Option<T>>,
     // Used to keep track of which endpoint should be called to
     // get subsequent pages.
@@ -221,6 +411,18 @@ impl<T: Clone + DeserializeOwned, E: Endpoint + Default + Clone> CursorPage<T, E
         self.items.clone().into_iter().flatten().collect()
     }
 
+    /// Whether or not there are pages after this one, based on the `next`
+    /// URL and the `after` cursor, regardless of whether `total` is present.
+    pub fn has_more(&self) -> bool {
+        self.next.is_some() || self.cursors.as_ref().is_some_and(|c| c.after.is_some())
+    }
+
+    /// Whether or not there are pages before this one, based on the `before`
+    /// cursor.
+    pub fn has_before(&self) -> bool {
+        self.cursors.as_ref().is_some_and(|c| c.before.is_some())
+    }
+
     /// Get the page chronologically before the current one.
     ///
     /// If there is no previous page, this will return an
@@ -247,12 +449,15 @@ impl<T: Clone + DeserializeOwned, E: Endpoint + Default + Clone> CursorPage<T, E
     /// If there is no next page, this will return an
     /// [`Error::NoRemainingPages`](crate::error::Error::NoRemainingPages).
     pub async fn get_after(&self, spotify: &Client<Token, impl AuthFlow>) -> Result<Self> {
-        let Some(ref cursors) = self.cursors else {
-            return Err(Error::NoRemainingPages);
-        };
-
-        let Some(after) = cursors.after.as_ref() else {
-            return Err(Error::NoRemainingPages);
+        let after = self.cursors.as_ref().and_then(|c| c.after.as_ref());
+
+        let Some(after) = after else {
+            // Some pages (e.g. empty ones) have no cursors, but still link to
+            // the next page.
+            return match self.next.as_ref() {
+                Some(next) => spotify.get::<(), _>(spotify.relative_url(next), None).await,
+                None => Err(Error::NoRemainingPages),
+            };
         };
 
         spotify
@@ -265,91 +470,122 @@ impl<T: Clone + DeserializeOwned, E: Endpoint + Default + Clone> CursorPage<T, E
 
     /// Get the items of all the remaining pages - that is, all the pages found
     /// after the current one.
+    ///
+    /// Pagination only stops when there is no next page, so empty pages
+    /// (which Spotify sometimes returns for filtered results) don't end it early.
+    ///
+    /// The pages are requested with a limit of `50` items. To use a different
+    /// limit, see [`get_remaining_with_limit`](Self::get_remaining_with_limit).
     pub async fn get_remaining(
+        self,
+        spotify: &Client<Token, impl AuthFlow>,
+    ) -> Result<Vec<Option<T>>> {
+        self.get_remaining_with_limit(PAGE_MAX_LIMIT, spotify).await
+    }
+
+    /// Get the items of all the remaining pages - that is, all the pages found
+    /// after the current one, requesting `limit` items per page.
+    ///
+    /// The limit is clamped between `1` - `50`, the maximum every endpoint
+    /// returning cursor-based pages accepts.
+    pub async fn get_remaining_with_limit(
         mut self,
+        limit: u32,
         spotify: &Client<Token, impl AuthFlow>,
     ) -> Result<Vec<Option<T>>> {
         let mut items = std::mem::take(&mut self.items);
-        self.limit = PAGE_MAX_LIMIT;
+        self.limit = limit.clamp(1, PAGE_MAX_LIMIT);
         let mut page = self;
 
         // Get all the next pages (if any)
-        if let Some(ref cursors) = page.cursors {
-            if cursors.after.is_some() {
-                loop {
-                    let next_page = page.get_after(spotify).await;
-
-                    match next_page {
-                        Ok(mut p) => {
-                            items.append(&mut p.items);
-                            page = p;
-                        }
-                        Err(err) => match err {
-                            Error::NoRemainingPages => break,
-                            _ => return Err(err),
-                        },
-                    }
+        if Paginated::has_more(&page) {
+            loop {
+                let next_page = page.get_after(spotify).await;
 
-                    tokio::time::sleep(PAGINATION_INTERVAL).await;
+                match next_page {
+                    Ok(mut p) => {
+                        items.append(&mut p.items);
+                        page = p;
+                    }
+                    Err(err) => match err {
+                        Error::NoRemainingPages => break,
+                        _ => return Err(err),
+                    },
                 }
+
+                tokio::time::sleep(PAGINATION_INTERVAL).await;
             }
         }
 
         Ok(items)
     }
 
-    pub async fn get_all(
+    /// Get the items of all of the pages - that is, all the pages found both before and
+    /// after the current one.
+    ///
+    /// The pages are requested with the same limit as the current page. To use
+    /// a different limit, see [`get_all_with_limit`](Self::get_all_with_limit).
+    pub async fn get_all(self, spotify: &Client<Token, impl AuthFlow>) -> Result<Vec<Option<T>>> {
+        let limit = self.limit;
+        self.get_all_with_limit(limit, spotify).await
+    }
+
+    /// Get the items of all of the pages - that is, all the pages found both before and
+    /// after the current one, requesting `limit` items per page.
+    ///
+    /// The limit is clamped between `1` - `50`, the maximum every endpoint
+    /// returning cursor-based pages accepts.
+    pub async fn get_all_with_limit(
         mut self,
+        limit: u32,
         spotify: &Client<Token, impl AuthFlow>,
     ) -> Result<Vec<Option<T>>> {
         let mut items = std::mem::take(&mut self.items);
-        self.limit = PAGE_MAX_LIMIT;
+        self.limit = limit.clamp(1, PAGE_MAX_LIMIT);
 
         // Get all the previous pages (if any)
-        if let Some(ref cursors) = self.cursors {
-            if cursors.before.is_some() {
-                let mut page = self.clone();
-
-                loop {
-                    let previous_page = page.get_before(spotify).await;
-
-                    match previous_page {
-                        Ok(mut p) => {
-                            items.append(&mut p.items);
-                            page = p;
-                        }
-                        Err(err) => match err {
-                            Error::NoRemainingPages => break,
-                            _ => return Err(err),
-                        },
-                    }
+        if let Some(ref cursors) = self.cursors
+            && cursors.before.is_some()
+        {
+            let mut page = self.clone();
 
-                    tokio::time::sleep(PAGINATION_INTERVAL).await;
+            loop {
+                let previous_page = page.get_before(spotify).await;
+
+                match previous_page {
+                    Ok(mut p) => {
+                        items.append(&mut p.items);
+                        page = p;
+                    }
+                    Err(err) => match err {
+                        Error::NoRemainingPages => break,
+                        _ => return Err(err),
+                    },
                 }
+
+                tokio::time::sleep(PAGINATION_INTERVAL).await;
             }
         }
 
         // Get all the next pages (if any)
-        if let Some(ref cursors) = self.cursors {
-            if cursors.after.is_some() {
-                let mut page = self;
-
-                loop {
-                    let next_page = page.get_after(spotify).await;
-
-                    match next_page {
-                        Ok(mut p) => {
-                            items.append(&mut p.items);
-                            page = p;
-                        }
-                        Err(err) => match err {
-                            Error::NoRemainingPages => break,
-                            _ => return Err(err),
-                        },
-                    }
+        if Paginated::has_more(&self) {
+            let mut page = self;
 
-                    tokio::time::sleep(PAGINATION_INTERVAL).await;
+            loop {
+                let next_page = page.get_after(spotify).await;
+
+                match next_page {
+                    Ok(mut p) => {
+                        items.append(&mut p.items);
+                        page = p;
+                    }
+                    Err(err) => match err {
+                        Error::NoRemainingPages => break,
+                        _ => return Err(err),
+                    },
                 }
+
+                tokio::time::sleep(PAGINATION_INTERVAL).await;
             }
         }
 
@@ -357,13 +593,112 @@ impl<T: Clone + DeserializeOwned, E: Endpoint + Default + Clone> CursorPage<T, E
     }
 }
 
+/// A page of items that can be paginated forward, implemented by both [`Page`]
+/// and [`CursorPage`], which allows writing code that works with either.
+///
+/// To get the items of a page and all the pages after it, use [`collect_all`].
+pub trait Paginated: Sized {
+    /// The type of the items in the page.
+    type Item;
+
+    /// Get a list of only the `Some` values from the page's items.
+    fn filtered_items(&self) -> Vec<Self::Item>;
+
+    /// Whether or not there are pages after this one.
+    fn has_more(&self) -> bool;
+
+    /// Get the next page.
+    ///
+    /// If there is no next page, this will return an
+    /// [`Error::NoRemainingPages`](crate::error::Error::NoRemainingPages).
+    fn next_page(
+        &self,
+        spotify: &Client<Token, impl AuthFlow>,
+    ) -> impl Future<Output = Result<Self>>;
+}
+
+impl<T: Clone + DeserializeOwned> Paginated for Page<T> {
+    type Item = T;
+
+    fn filtered_items(&self) -> Vec<T> {
+        self.filtered_items()
+    }
+
+    fn has_more(&self) -> bool {
+        self.next.is_some()
+    }
+
+    async fn next_page(&self, spotify: &Client<Token, impl AuthFlow>) -> Result<Self> {
+        self.get_next(spotify).await
+    }
+}
+
+impl<T: Clone + DeserializeOwned, E: Endpoint + Default + Clone> Paginated for CursorPage<T, E> {
+    type Item = T;
+
+    fn filtered_items(&self) -> Vec<T> {
+        self.filtered_items()
+    }
+
+    fn has_more(&self) -> bool {
+        self.has_more()
+    }
+
+    async fn next_page(&self, spotify: &Client<Token, impl AuthFlow>) -> Result<Self> {
+        self.get_after(spotify).await
+    }
+}
+
+/// Get the items of a page and all the pages after it, excluding `null` values,
+/// for either a [`Page`] or a [`CursorPage`].
+pub async fn collect_all<P: Paginated>(
+    page: P,
+    spotify: &Client<Token, impl AuthFlow>,
+) -> Result<Vec<P::Item>> {
+    let mut items = page.filtered_items();
+    let mut page = page;
+
+    while page.has_more() {
+        tokio::time::sleep(PAGINATION_INTERVAL).await;
+
+        page = page.next_page(spotify).await?;
+        items.append(&mut page.filtered_items());
+    }
+
+    Ok(items)
+}
+
 /// A cursor used to paginate results returned as a [`CursorPage`].
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct Cursor {
+    /// The cursor to use as a key to find the next page of items.
+    ///
+    /// Depending on the endpoint, it's either a Unix timestamp (in milliseconds)
+    /// or an opaque value (such as an ID).
     pub after: Option<String>,
+    /// The cursor to use as a key to find the previous page of items.
+    ///
+    /// Depending on the endpoint, it's either a Unix timestamp (in milliseconds)
+    /// or an opaque value (such as an ID).
     pub before: Option<String>,
 }
 
+impl Cursor {
+    /// Get the `after` cursor as a Unix timestamp in milliseconds.
+    ///
+    /// Returns `None` if there is no `after` cursor, or if it's not a timestamp.
+    pub fn after_timestamp(&self) -> Option<i64> {
+        self.after.as_ref().and_then(|a| a.parse().ok())
+    }
+
+    /// Get the `before` cursor as a Unix timestamp in milliseconds.
+    ///
+    /// Returns `None` if there is no `before` cursor, or if it's not a timestamp.
+    pub fn before_timestamp(&self) -> Option<i64> {
+        self.before.as_ref().and_then(|b| b.parse().ok())
+    }
+}
+
 /// An image used in various contexts.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct Image {
@@ -375,6 +710,35 @@ pub struct Image {
     pub width: Option<u32>,
 }
 
+impl Image {
+    // The area of the image in pixels, if its size is known.
+    fn area(&self) -> Option<u64> {
+        Some(u64::from(self.width?) * u64::from(self.height?))
+    }
+}
+
+/// Helpers for picking an image out of the images of an item (e.g. an
+/// album cover), which are usually returned in several sizes.
+pub trait Images {
+    /// Get the largest image. Images whose size is unknown are considered
+    /// smaller than all the others.
+    fn largest(&self) -> Option<&Image>;
+
+    /// Get the smallest image (e.g. to use as a thumbnail). Images whose size
+    /// is unknown are considered larger than all the others.
+    fn smallest(&self) -> Option<&Image>;
+}
+
+impl Images for [Image] {
+    fn largest(&self) -> Option<&Image> {
+        self.iter().max_by_key(|i| i.area().unwrap_or(0))
+    }
+
+    fn smallest(&self) -> Option<&Image> {
+        self.iter().min_by_key(|i| i.area().unwrap_or(u64::MAX))
+    }
+}
+
 /// A copyright statement.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct Copyright {
@@ -423,12 +787,40 @@ pub struct Followers {
     pub total: u32,
 }
 
+impl Followers {
+    /// Get the total amount of followers.
+    pub fn count(&self) -> u32 {
+        self.total
+    }
+
+    /// Get the amount of followers in a short, human-readable form, using
+    /// `K`, `M` and `B` suffixes with (at most) one decimal, e.g. `950`, `12.3K`
+    /// or `1.2M`.
+    ///
+    /// The amount is rounded down, so `999 999` followers are `999.9K`.
+    pub fn humanized(&self) -> String {
+        const SUFFIXES: [(u32, &str); 3] = [(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "K")];
+
+        let Some((unit, suffix)) = SUFFIXES.iter().find(|(unit, _)| self.total >= *unit) else {
+            return self.total.to_string();
+        };
+
+        // The amount in tenths of the unit, to keep one decimal.
+        let tenths = self.total / (unit / 10);
+
+        match tenths % 10 {
+            0 => format!("{}{suffix}", tenths / 10),
+            decimal => format!("{}.{decimal}{suffix}", tenths / 10),
+        }
+    }
+}
+
 /// The user's latest position in a chapter or episode.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct ResumePoint {
     /// Whether or not the chapter or episode has fully been played by the user.
     pub fully_played: bool,
-    /// The user's latest position in miliseconds.
+    /// The user's latest position in milliseconds.
     pub resume_position_ms: u32,
 }
 
@@ -449,6 +841,68 @@ pub enum RestrictionReason {
     Unknown,
 }
 
+/// The type of a Spotify object, which can be used to tell apart objects of
+/// different types (e.g. in a playlist's items or search results).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ObjectType {
+    /// An album.
+    Album,
+
+    /// An artist.
+    Artist,
+
+    /// An audiobook.
+    Audiobook,
+
+    /// An audiobook chapter.
+    Chapter,
+
+    /// A show episode.
+    Episode,
+
+    /// A playlist.
+    Playlist,
+
+    /// A show.
+    Show,
+
+    /// A track.
+    Track,
+
+    /// A user.
+    User,
+
+    /// Any other object type that may be added in the future.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl ObjectType {
+    /// Get the object type as it's named by Spotify (e.g. `track`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Album => "album",
+            Self::Artist => "artist",
+            Self::Audiobook => "audiobook",
+            Self::Chapter => "chapter",
+            Self::Episode => "episode",
+            Self::Playlist => "playlist",
+            Self::Show => "show",
+            Self::Track => "track",
+            Self::User => "user",
+            Self::Unknown(object_type) => object_type,
+        }
+    }
+}
+
+impl std::fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// The copyright type for a piece of content:
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub enum CopyrightType {
@@ -458,6 +912,9 @@ pub enum CopyrightType {
     #[serde(rename = "P")]
     /// The sound recording (performance) copyright.
     Performance,
+    #[serde(other)]
+    /// Any other type of copyright, as more may be added in the future.
+    Unknown,
 }
 
 /// The precision with which a date is known.
@@ -485,6 +942,357 @@ pub enum PlayableItem {
     Unknown(serde_json::Value),
 }
 
+/// An item that can be shared using a link to the Spotify web player,
+/// e.g. `https://open.spotify.com/track/{id}`.
+pub trait Shareable {
+    /// Get the web player URL of the item.
+    ///
+    /// If the item has no ID (e.g. local tracks), the Spotify URL from the
+    /// item's `external_urls` is returned instead.
+    fn web_url(&self) -> String;
+}
+
+// Build a web player URL from the type and ID of an item, falling back
+// to its external Spotify URL if the ID is missing.
+fn web_url(item_type: &str, id: &str, external_urls: &ExternalUrls) -> String {
+    if id.is_empty() {
+        external_urls.spotify.clone()
+    } else {
+        format!("{WEB_PLAYER_URL}/{item_type}/{id}")
+    }
+}
+
+macro_rules! impl_shareable {
+    ($item_type:literal => $($t:ty),+ $(,)?) => {
+        $(
+            impl Shareable for $t {
+                fn web_url(&self) -> String {
+                    web_url($item_type, &self.id, &self.external_urls)
+                }
+            }
+        )+
+    };
+}
+
+impl_shareable!("album" => album::Album, album::SimplifiedAlbum);
+impl_shareable!("artist" => artist::Artist, artist::SimplifiedArtist);
+impl_shareable!("audiobook" => audiobook::Audiobook, audiobook::SimplifiedAudiobook);
+impl_shareable!("chapter" => audiobook::Chapter, audiobook::SimplifiedChapter);
+impl_shareable!("episode" => show::Episode, show::SimplifiedEpisode);
+impl_shareable!("playlist" => playlist::Playlist, playlist::SimplifiedPlaylist);
+impl_shareable!("show" => show::Show, show::SimplifiedShow);
+impl_shareable!("track" => track::Track, track::SimplifiedTrack);
+impl_shareable!("user" => user::User, user::PrivateUser, user::ReferenceUser);
+
+/// An item that may contain explicit content, such as a track or episode.
+pub trait Explicit {
+    /// Whether or not the item has explicit content.
+    fn is_explicit(&self) -> bool;
+}
+
+macro_rules! impl_explicit {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl Explicit for $t {
+                fn is_explicit(&self) -> bool {
+                    self.explicit
+                }
+            }
+        )+
+    };
+}
+
+impl_explicit!(audiobook::Audiobook, audiobook::SimplifiedAudiobook);
+impl_explicit!(audiobook::Chapter, audiobook::SimplifiedChapter);
+impl_explicit!(show::Episode, show::SimplifiedEpisode);
+impl_explicit!(show::Show, show::SimplifiedShow);
+impl_explicit!(track::Track, track::SimplifiedTrack);
+
+/// An item with a Spotify URI that can be used with other endpoints, such as
+/// starting playback or adding items to a playlist.
+pub trait HasUri {
+    /// Get the Spotify URI of the item.
+    ///
+    /// Returns `None` if the URI can't be used with other endpoints, such as
+    /// for local files (see [`is_local_uri`]).
+    fn spotify_uri(&self) -> Option<&str>;
+}
+
+macro_rules! impl_has_uri {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl HasUri for $t {
+                fn spotify_uri(&self) -> Option<&str> {
+                    Some(self.uri.as_str()).filter(|uri| !is_local_uri(uri))
+                }
+            }
+        )+
+    };
+}
+
+impl_has_uri!(album::Album, album::SimplifiedAlbum);
+impl_has_uri!(artist::Artist, artist::SimplifiedArtist);
+impl_has_uri!(audiobook::Audiobook, audiobook::SimplifiedAudiobook);
+impl_has_uri!(audiobook::Chapter, audiobook::SimplifiedChapter);
+impl_has_uri!(playlist::Playlist, playlist::SimplifiedPlaylist);
+impl_has_uri!(show::Episode, show::SimplifiedEpisode);
+impl_has_uri!(show::Show, show::SimplifiedShow);
+impl_has_uri!(track::Track, track::SimplifiedTrack);
+
+impl HasUri for PlayableItem {
+    fn spotify_uri(&self) -> Option<&str> {
+        match self {
+            Self::Track(track) => track.spotify_uri(),
+            Self::Episode(episode) => episode.spotify_uri(),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl HasUri for playlist::PlaylistItem {
+    fn spotify_uri(&self) -> Option<&str> {
+        self.track.spotify_uri()
+    }
+}
+
+/// An item that's only available in some markets, such as an album, track,
+/// show or audiobook.
+///
+/// With the `skip-available-markets` feature enabled, the markets are never
+/// deserialized, so [`markets`](Self::markets) and
+/// [`available_markets_set`](Self::available_markets_set) are always empty,
+/// and [`is_available_in`](Self::is_available_in) is always `false`. Use
+/// [`markets_skipped`](Self::markets_skipped) to tell that apart from an item
+/// that isn't available anywhere.
+pub trait Available {
+    /// The [ISO 3166-1 alpha-2](https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2)
+    /// codes of the markets the item is available in, which is empty if they
+    /// weren't returned, or were skipped (see [`markets_skipped`](Self::markets_skipped)).
+    fn markets(&self) -> &[String];
+
+    /// Whether or not the markets of the item were skipped when deserializing
+    /// it, which is the case when the `skip-available-markets` feature is
+    /// enabled.
+    fn markets_skipped(&self) -> bool {
+        cfg!(feature = "skip-available-markets")
+    }
+
+    /// Get the markets the item is available in as a set, for fast lookups
+    /// when checking many markets.
+    fn available_markets_set(&self) -> HashSet<&str> {
+        self.markets().iter().map(String::as_str).collect()
+    }
+
+    /// Whether or not the item is available in the given market.
+    ///
+    /// If the item was requested with a market, Spotify omits its markets, so
+    /// this returns `false` for every market. In that case, use
+    /// [`is_available_in_requested_market`](Self::is_available_in_requested_market)
+    /// instead.
+    fn is_available_in(&self, market: &str) -> bool {
+        self.markets().iter().any(|m| m == market)
+    }
+
+    /// Whether or not the item is available in the market it was requested
+    /// with, which Spotify indicates through whether or not it's
+    /// [playable](Playable::playable).
+    ///
+    /// Returns `None` if the item wasn't requested with a market (see
+    /// [`was_market_filtered`](Self::was_market_filtered)), in which case use
+    /// [`is_available_in`](Self::is_available_in).
+    fn is_available_in_requested_market(&self) -> Option<bool> {
+        None
+    }
+
+    /// Whether or not the item was requested with a market, in which case
+    /// Spotify only returns content available in that market (or relinked to
+    /// a version that is), and omits the markets the item is available in.
+    ///
+    /// This can only be detected for tracks, so it's always `false` for
+    /// other items.
+    fn was_market_filtered(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_available {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl Available for $t {
+                fn markets(&self) -> &[String] {
+                    &self.available_markets
+                }
+            }
+        )+
+    };
+}
+
+impl_available!(album::Album, album::SimplifiedAlbum);
+impl_available!(audiobook::Audiobook, audiobook::SimplifiedAudiobook);
+impl_available!(audiobook::Chapter, audiobook::SimplifiedChapter);
+impl_available!(show::Show, show::SimplifiedShow);
+
+// Tracks only have an `is_playable` field if they were requested with a market.
+impl Available for track::Track {
+    fn markets(&self) -> &[String] {
+        self.available_markets.as_deref().unwrap_or_default()
+    }
+
+    fn was_market_filtered(&self) -> bool {
+        self.is_playable.is_some()
+    }
+
+    fn is_available_in_requested_market(&self) -> Option<bool> {
+        self.is_playable
+    }
+}
+
+impl Available for track::SimplifiedTrack {
+    fn markets(&self) -> &[String] {
+        self.available_markets.as_deref().unwrap_or_default()
+    }
+
+    fn was_market_filtered(&self) -> bool {
+        self.is_playable.is_some()
+    }
+
+    fn is_available_in_requested_market(&self) -> Option<bool> {
+        self.is_playable
+    }
+}
+
+/// An item that can have a content restriction applied, such as an album,
+/// track, episode or chapter.
+pub trait Restricted {
+    /// The content restriction applied to the item, if any, which explains
+    /// why it isn't available.
+    fn restriction(&self) -> Option<&Restriction>;
+
+    /// The reason for the content restriction applied to the item, if any.
+    fn restriction_reason(&self) -> Option<RestrictionReason> {
+        self.restriction().map(|r| r.reason.clone())
+    }
+}
+
+/// An item whose playability depends on the market and licensing, such as a
+/// track, episode or chapter.
+pub trait Playable: Restricted {
+    /// Whether or not the item is playable, or `None` if it's unknown.
+    ///
+    /// Spotify only returns it when a market is supplied, e.g. for tracks
+    /// it's part of the response when
+    /// [Track Relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
+    /// is applied. To treat a missing value as playable, use
+    /// [`is_playable_or_unknown`](Playable::is_playable_or_unknown).
+    fn playable(&self) -> Option<bool>;
+
+    /// Whether or not playing the item should be attempted.
+    ///
+    /// Spotify only says whether an item is playable when a market is
+    /// supplied, so this assumes the item is playable when it's unknown, and
+    /// otherwise returns the flag Spotify returned.
+    fn is_playable_or_unknown(&self) -> bool {
+        self.playable().unwrap_or(true)
+    }
+}
+
+macro_rules! impl_restricted {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl Restricted for $t {
+                fn restriction(&self) -> Option<&Restriction> {
+                    self.restrictions.as_ref()
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_playable {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl_restricted!($t);
+
+            impl Playable for $t {
+                fn playable(&self) -> Option<bool> {
+                    self.is_playable
+                }
+            }
+        )+
+    };
+}
+
+impl_restricted!(album::Album, album::SimplifiedAlbum);
+
+impl_playable!(audiobook::Chapter, audiobook::SimplifiedChapter);
+impl_playable!(show::Episode, show::SimplifiedEpisode);
+impl_playable!(track::Track, track::SimplifiedTrack);
+
+impl Restricted for PlayableItem {
+    fn restriction(&self) -> Option<&Restriction> {
+        match self {
+            Self::Track(track) => track.restriction(),
+            Self::Episode(episode) => episode.restriction(),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl Playable for PlayableItem {
+    fn playable(&self) -> Option<bool> {
+        match self {
+            Self::Track(track) => track.playable(),
+            Self::Episode(episode) => episode.playable(),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+/// Whether or not a Spotify URI refers to a local file (e.g. `spotify:local:...`),
+/// which can't be used with most endpoints, such as adding items to a playlist.
+pub fn is_local_uri(uri: &str) -> bool {
+    uri.starts_with("spotify:local:")
+}
+
+// Deserialize each item of a page independently, so that a single malformed
+// item becomes `None` instead of failing the entire page.
+fn lenient_items<'de, T, D>(deserializer: D) -> Result<Vec<Option<T>>, D::Error>
+where
+    T: DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    let values = Vec::<serde_json::Value>::deserialize(deserializer)?;
+
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            if value.is_null() {
+                return None;
+            }
+
+            serde_json::from_value(value)
+                .inspect_err(|err| tracing::warn!(%err, "Skipping a malformed page item."))
+                .ok()
+        })
+        .collect())
+}
+
+// Deserialize an optional value, treating an empty object (which Spotify
+// sometimes sends instead of `null`) as `None`.
+fn lenient_option<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Object(map)) if map.is_empty() => Ok(None),
+        Some(value) => serde_json::from_value(value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
 // A function to convert a "null" JSON value to the default of given type,
 // to make the API slightly nicer to use for people.
 fn null_to_default<'de, T, D>(deserializer: D) -> Result<T, D::Error>
@@ -494,3 +1302,245 @@ where
 {
     Ok(Option::deserialize(deserializer)?.unwrap_or_default())
 }
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Method;
+    use serde::Serialize;
+    use serde_json::json;
+
+    use super::*;
+    use crate::client::mock::MockTransport;
+
+    #[derive(Clone, Debug, Default, Serialize)]
+    struct TestEndpoint;
+
+    impl Endpoint for TestEndpoint {
+        fn endpoint_url(&self) -> &'static str {
+            "/test/cursor"
+        }
+    }
+
+    #[tokio::test]
+    async fn page_remaining_continues_after_empty_page() {
+        let transport = MockTransport::new(|req| {
+            let page = match req.query("offset").as_deref() {
+                Some("2") => json!({
+                    "href": "https://api.spotify.com/v1/test?offset=2",
+                    "limit": 2,
+                    "next": "https://api.spotify.com/v1/test?offset=4",
+                    "offset": 2,
+                    "previous": "https://api.spotify.com/v1/test?offset=0",
+                    "total": 5,
+                    "items": []
+                }),
+                Some("4") => json!({
+                    "href": "https://api.spotify.com/v1/test?offset=4",
+                    "limit": 2,
+                    "next": null,
+                    "offset": 4,
+                    "previous": "https://api.spotify.com/v1/test?offset=2",
+                    "total": 5,
+                    "items": ["c"]
+                }),
+                _ => return (404, String::new()),
+            };
+
+            (200, page.to_string())
+        });
+        let spotify = transport.client();
+
+        let first: Page<String> = serde_json::from_value(json!({
+            "href": "https://api.spotify.com/v1/test?offset=0",
+            "limit": 2,
+            "next": "https://api.spotify.com/v1/test?offset=2",
+            "offset": 0,
+            "previous": null,
+            "total": 5,
+            "items": ["a", "b"]
+        }))
+        .unwrap();
+
+        let items = first.get_remaining(&spotify).await.unwrap();
+
+        assert_eq!(items, ["a", "b", "c"].map(|i| Some(i.to_owned())));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|r| r.method == Method::GET));
+    }
+
+    #[tokio::test]
+    async fn page_limit_is_clamped_to_the_endpoint_maximum() {
+        let transport = MockTransport::new(|req| {
+            let href = format!("https://api.spotify.com{}", req.path_and_query);
+            let page = json!({
+                "href": href,
+                "limit": 100,
+                "next": null,
+                "offset": 1,
+                "previous": null,
+                "total": 2,
+                "items": ["b"]
+            });
+
+            (200, page.to_string())
+        });
+        let spotify = transport.client();
+
+        for (path, expected_limit) in [
+            ("/v1/playlists/playlist/tracks", "100"),
+            ("/v1/me/tracks", "50"),
+        ] {
+            let href = format!("https://api.spotify.com{path}");
+            let first: Page<String> = serde_json::from_value(json!({
+                "href": format!("{href}?offset=0&limit=1"),
+                "limit": 1,
+                "next": format!("{href}?offset=1"),
+                "offset": 0,
+                "previous": null,
+                "total": 2,
+                "items": ["a"]
+            }))
+            .unwrap();
+
+            first.get_all_with_limit(100, &spotify).await.unwrap();
+
+            let request = transport.requests().pop().unwrap();
+            assert_eq!(request.query("limit").as_deref(), Some(expected_limit));
+        }
+    }
+
+    #[tokio::test]
+    async fn cursor_page_remaining_continues_after_empty_page() {
+        let transport = MockTransport::new(|req| {
+            let page = match req.query("after").as_deref() {
+                // An empty page without cursors, which only links to the next one.
+                Some("cursor1") => json!({
+                    "href": "https://api.spotify.com/v1/test/cursor?after=cursor1",
+                    "limit": 2,
+                    "next": "https://api.spotify.com/v1/test/cursor?after=cursor2",
+                    "cursors": null,
+                    "items": []
+                }),
+                Some("cursor2") => json!({
+                    "href": "https://api.spotify.com/v1/test/cursor?after=cursor2",
+                    "limit": 2,
+                    "next": null,
+                    "cursors": { "after": null, "before": "cursor2" },
+                    "items": ["c"]
+                }),
+                _ => return (404, String::new()),
+            };
+
+            (200, page.to_string())
+        });
+        let spotify = transport.client();
+
+        let first: CursorPage<String, TestEndpoint> = serde_json::from_value(json!({
+            "href": "https://api.spotify.com/v1/test/cursor",
+            "limit": 2,
+            "next": "https://api.spotify.com/v1/test/cursor?after=cursor1",
+            "cursors": { "after": "cursor1", "before": null },
+            "items": ["a", "b"]
+        }))
+        .unwrap();
+
+        let items = first.get_remaining(&spotify).await.unwrap();
+
+        assert_eq!(items, ["a", "b", "c"].map(|i| Some(i.to_owned())));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|r| r.method == Method::GET));
+    }
+
+    fn simplified_track(
+        available_markets: Option<&[&str]>,
+        is_playable: Option<bool>,
+    ) -> track::SimplifiedTrack {
+        serde_json::from_value(json!({
+            "artists": [],
+            "available_markets": available_markets,
+            "disc_number": 1,
+            "duration_ms": 1000,
+            "explicit": false,
+            "external_urls": { "spotify": "https://open.spotify.com/track/track" },
+            "href": "https://api.spotify.com/v1/tracks/track",
+            "id": "track",
+            "is_playable": is_playable,
+            "name": "Track",
+            "track_number": 1,
+            "type": "track",
+            "uri": "spotify:track:track",
+            "is_local": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn availability_of_market_filtered_track() {
+        let track = simplified_track(None, Some(true));
+        assert!(track.was_market_filtered());
+        assert_eq!(track.is_available_in_requested_market(), Some(true));
+
+        let track = simplified_track(None, Some(false));
+        assert_eq!(track.is_available_in_requested_market(), Some(false));
+    }
+
+    #[test]
+    #[cfg(not(feature = "skip-available-markets"))]
+    fn availability_of_unfiltered_track() {
+        let track = simplified_track(Some(&["GB", "US"]), None);
+
+        assert!(!track.was_market_filtered());
+        assert_eq!(track.is_available_in_requested_market(), None);
+        assert!(track.is_available_in("GB"));
+        assert!(!track.is_available_in("FR"));
+    }
+
+    #[test]
+    fn copyright_type_known_variants() {
+        let copyright: Copyright =
+            serde_json::from_str(r#"{ "text": "(C) 2024 Label", "type": "C" }"#).unwrap();
+        assert_eq!(copyright.r#type, CopyrightType::Copyright);
+
+        let copyright: Copyright =
+            serde_json::from_str(r#"{ "text": "(P) 2024 Label", "type": "P" }"#).unwrap();
+        assert_eq!(copyright.r#type, CopyrightType::Performance);
+    }
+
+    #[test]
+    fn copyright_type_unexpected_is_unknown() {
+        let copyright: Copyright =
+            serde_json::from_str(r#"{ "text": "2024 Label", "type": "X" }"#).unwrap();
+
+        assert_eq!(copyright.r#type, CopyrightType::Unknown);
+        assert_eq!(copyright.text, "2024 Label");
+    }
+
+    #[test]
+    fn resume_point_is_none_only_when_missing_or_empty() {
+        #[derive(Debug, Deserialize)]
+        struct Episode {
+            #[serde(default, deserialize_with = "lenient_option")]
+            resume_point: Option<ResumePoint>,
+        }
+
+        let parse = |json| serde_json::from_value::<Episode>(json).map(|e| e.resume_point);
+
+        assert_eq!(parse(json!({})).unwrap(), None);
+        assert_eq!(parse(json!({ "resume_point": null })).unwrap(), None);
+        assert_eq!(parse(json!({ "resume_point": {} })).unwrap(), None);
+        assert_eq!(
+            parse(json!({ "resume_point": { "fully_played": true, "resume_position_ms": 10 } }))
+                .unwrap(),
+            Some(ResumePoint {
+                fully_played: true,
+                resume_position_ms: 10
+            })
+        );
+        // Anything else that can't be deserialized is still an error.
+        assert!(parse(json!({ "resume_point": { "fully_played": "yes" } })).is_err());
+    }
+}
@@ -13,6 +13,7 @@ pub struct Album {
     /// The number of tracks in the album.
     pub total_tracks: u32,
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     pub external_urls: ExternalUrls,
     pub href: String,
@@ -21,7 +22,8 @@ pub struct Album {
     pub name: String,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
-    pub r#type: String,
+    pub restrictions: Option<Restriction>,
+    pub r#type: ObjectType,
     pub uri: String,
     pub copyrights: Vec<Copyright>,
     pub external_ids: ExternalIds,
@@ -32,13 +34,29 @@ pub struct Album {
     pub label: String,
     /// A number ranging between `1` - `100` that represents the popularity of
     /// the album.
-    pub popularity: u32,
+    ///
+    /// It's `None` if Spotify doesn't include it in the response, which is
+    /// different from a popularity of `0`.
+    pub popularity: Option<u8>,
     /// The artists of the album.
     pub artists: Vec<SimplifiedArtist>,
     /// The tracks of the album.
     pub tracks: Page<SimplifiedTrack>,
 }
 
+impl Album {
+    /// Get the names of the album's artists, separated by ", " (e.g.
+    /// `Artist A, Artist B`).
+    pub fn artists_string(&self) -> String {
+        artist::join_names(&self.artists)
+    }
+
+    /// Get the primary (first listed) artist of the album.
+    pub fn primary_artist(&self) -> Option<&SimplifiedArtist> {
+        self.artists.first()
+    }
+}
+
 /// A simplified album, missing some details, that is usually obtained
 /// through endpoints not specific to albums. The `href` field may be
 /// used to get a full album.
@@ -50,6 +68,7 @@ pub struct SimplifiedAlbum {
     /// The number of tracks in the album.
     pub total_tracks: u32,
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     pub external_urls: ExternalUrls,
     pub href: String,
@@ -59,7 +78,7 @@ pub struct SimplifiedAlbum {
     pub release_date: String,
     pub release_date_precision: DatePrecision,
     pub restrictions: Option<Restriction>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// The field is present when getting an artist's albums. Compared to album_type
     /// this field represents the relationship between the artist and the album.
@@ -68,6 +87,26 @@ pub struct SimplifiedAlbum {
     pub artists: Vec<SimplifiedArtist>,
 }
 
+impl SimplifiedAlbum {
+    /// Get the full album, which includes its tracks, popularity, genres etc.
+    ///
+    /// This will make a request to get the album using its ID.
+    pub async fn fetch_full(&self, spotify: &Client<Token, impl AuthFlow>) -> Result<Album> {
+        crate::endpoint::album::album(&self.id).get(spotify).await
+    }
+
+    /// Get the names of the album's artists, separated by ", " (e.g.
+    /// `Artist A, Artist B`).
+    pub fn artists_string(&self) -> String {
+        artist::join_names(&self.artists)
+    }
+
+    /// Get the primary (first listed) artist of the album.
+    pub fn primary_artist(&self) -> Option<&SimplifiedArtist> {
+        self.artists.first()
+    }
+}
+
 /// An album saved by a user.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct SavedAlbum {
@@ -80,7 +119,7 @@ pub struct SavedAlbum {
 // Used only to deserialize JSON responses with arrays that are named objects.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub(crate) struct Albums {
-    pub(crate) albums: Vec<Album>,
+    pub(crate) albums: Vec<Option<Album>>,
 }
 
 // Used only to deserialize JSON responses with arrays that are named objects.
@@ -19,8 +19,11 @@ pub struct Artist {
     pub images: Vec<Image>,
     pub name: String,
     /// The popularity of the artist, represented as a number between 1 - 100.
-    pub popularity: u32,
-    pub r#type: String,
+    ///
+    /// It's `None` if Spotify doesn't include it in the response, which is
+    /// different from a popularity of `0`.
+    pub popularity: Option<u8>,
+    pub r#type: ObjectType,
     pub uri: String,
 }
 
@@ -34,14 +37,32 @@ pub struct SimplifiedArtist {
     pub href: String,
     pub id: String,
     pub name: String,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
 }
 
+impl SimplifiedArtist {
+    /// Get the full artist, which includes their followers, genres, images etc.
+    ///
+    /// This will make a request to get the artist using their ID.
+    pub async fn fetch_full(&self, spotify: &Client<Token, impl AuthFlow>) -> Result<Artist> {
+        crate::endpoint::artist::get_artist(&self.id, spotify).await
+    }
+}
+
+// Join the names of the artists with ", ", e.g. "Artist A, Artist B".
+pub(crate) fn join_names(artists: &[SimplifiedArtist]) -> String {
+    artists
+        .iter()
+        .map(|a| a.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 // Used only to deserialize JSON responses with arrays that are named objects.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub(crate) struct Artists {
-    pub(crate) artists: Vec<Artist>,
+    pub(crate) artists: Vec<Option<Artist>>,
 }
 
 // Used only to deserialize JSON responses with arrays that are named objects.
@@ -87,6 +87,68 @@ pub struct AudioFeatures {
     pub valence: f32,
 }
 
+impl AudioFeatures {
+    /// Get the key of the track in [Camelot wheel](https://en.wikipedia.org/wiki/Camelot_Wheel)
+    /// notation (e.g. `8B` for C major and `8A` for A minor), which is used
+    /// for harmonic mixing.
+    ///
+    /// Returns `None` if no key was detected.
+    pub fn camelot_key(&self) -> Option<String> {
+        self.camelot()
+            .map(|(number, letter)| format!("{number}{letter}"))
+    }
+
+    /// Whether or not the track can be mixed harmonically with another one,
+    /// which is the case if their keys are the same, adjacent on the Camelot
+    /// wheel (e.g. `8B` and `9B`), or relative major and minor keys (e.g. `8B`
+    /// and `8A`).
+    ///
+    /// Returns `false` if the key of either track wasn't detected.
+    pub fn is_harmonically_compatible(&self, other: &AudioFeatures) -> bool {
+        let (Some((number, letter)), Some((other_number, other_letter))) =
+            (self.camelot(), other.camelot())
+        else {
+            return false;
+        };
+
+        // The distance between the numbers, on a wheel of 12.
+        let distance = number.abs_diff(other_number);
+        let distance = distance.min(12 - distance);
+
+        if letter == other_letter {
+            distance <= 1
+        } else {
+            distance == 0
+        }
+    }
+
+    /// Whether or not the tempo of the track is within `tolerance` (a fraction,
+    /// e.g. `0.06` for 6%) of the tempo of another one, also allowing for
+    /// double and half time.
+    pub fn is_tempo_compatible(&self, other: &AudioFeatures, tolerance: f32) -> bool {
+        if self.tempo <= 0.0 || other.tempo <= 0.0 {
+            return false;
+        }
+
+        [1.0, 2.0, 0.5]
+            .iter()
+            .any(|factor| (self.tempo / (other.tempo * factor) - 1.0).abs() <= tolerance)
+    }
+
+    // Get the Camelot wheel number (`1` - `12`) and letter (`A` for minor
+    // and `B` for major keys) of the track's key.
+    fn camelot(&self) -> Option<(u8, char)> {
+        let key = u8::try_from(self.key).ok().filter(|k| *k < 12)?;
+
+        // Going up a fifth (7 semitones) moves one step around the wheel,
+        // where C major is `8B` and C minor is `5A`.
+        Some(match self.mode {
+            Mode::Major => ((7 * key + 7) % 12 + 1, 'B'),
+            Mode::Minor => ((7 * key + 4) % 12 + 1, 'A'),
+        })
+    }
+}
+
 // Used only to deserialize JSON responses with arrays that are named objects.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub(crate) struct AudioFeaturesList {
@@ -324,3 +386,108 @@ pub enum AnalysisStatusCode {
     Success,
     Failure,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(key: i32, mode: Mode) -> AudioFeatures {
+        AudioFeatures {
+            acousticness: 0.0,
+            analysis_url: String::new(),
+            danceability: 0.0,
+            duration_ms: 0,
+            energy: 0.0,
+            id: String::new(),
+            instrumentalness: 0.0,
+            key,
+            liveness: 0.0,
+            loudness: 0.0,
+            mode,
+            speechiness: 0.0,
+            tempo: 120.0,
+            time_signature: 4,
+            track_href: String::new(),
+            r#type: "audio_features".to_owned(),
+            uri: String::new(),
+            valence: 0.0,
+        }
+    }
+
+    #[test]
+    fn camelot_key_covers_every_key_and_mode() {
+        let cases = [
+            (0, Mode::Major, "8B"),
+            (1, Mode::Major, "3B"),
+            (2, Mode::Major, "10B"),
+            (3, Mode::Major, "5B"),
+            (4, Mode::Major, "12B"),
+            (5, Mode::Major, "7B"),
+            (6, Mode::Major, "2B"),
+            (7, Mode::Major, "9B"),
+            (8, Mode::Major, "4B"),
+            (9, Mode::Major, "11B"),
+            (10, Mode::Major, "6B"),
+            (11, Mode::Major, "1B"),
+            (0, Mode::Minor, "5A"),
+            (1, Mode::Minor, "12A"),
+            (2, Mode::Minor, "7A"),
+            (3, Mode::Minor, "2A"),
+            (4, Mode::Minor, "9A"),
+            (5, Mode::Minor, "4A"),
+            (6, Mode::Minor, "11A"),
+            (7, Mode::Minor, "6A"),
+            (8, Mode::Minor, "1A"),
+            (9, Mode::Minor, "8A"),
+            (10, Mode::Minor, "3A"),
+            (11, Mode::Minor, "10A"),
+        ];
+
+        for (key, mode, expected) in cases {
+            assert_eq!(
+                features(key, mode).camelot_key().as_deref(),
+                Some(expected),
+                "key {key}, mode {mode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn camelot_key_is_none_for_unknown_key() {
+        assert_eq!(features(-1, Mode::Major).camelot_key(), None);
+        assert_eq!(features(-1, Mode::Minor).camelot_key(), None);
+    }
+
+    #[test]
+    fn unknown_key_is_never_harmonically_compatible() {
+        let unknown = features(-1, Mode::Major);
+
+        assert!(!unknown.is_harmonically_compatible(&unknown));
+        assert!(!unknown.is_harmonically_compatible(&features(0, Mode::Major)));
+    }
+
+    #[test]
+    fn harmonic_compatibility_wraps_around_the_wheel() {
+        // C# minor (12A) and G# minor (1A).
+        let twelve_a = features(1, Mode::Minor);
+        let one_a = features(8, Mode::Minor);
+
+        assert!(twelve_a.is_harmonically_compatible(&one_a));
+        assert!(one_a.is_harmonically_compatible(&twelve_a));
+    }
+
+    #[test]
+    fn harmonic_compatibility_of_neighbours() {
+        // C major (8B).
+        let c_major = features(0, Mode::Major);
+
+        // Same key, G major (9B), A minor (8A).
+        assert!(c_major.is_harmonically_compatible(&c_major));
+        assert!(c_major.is_harmonically_compatible(&features(7, Mode::Major)));
+        assert!(c_major.is_harmonically_compatible(&features(9, Mode::Minor)));
+
+        // D major (10B), E minor (9A).
+        assert!(!c_major.is_harmonically_compatible(&features(2, Mode::Major)));
+        assert!(!c_major.is_harmonically_compatible(&features(4, Mode::Minor)));
+    }
+}
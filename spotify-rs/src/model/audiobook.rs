@@ -10,6 +10,7 @@ pub struct Audiobook {
     /// The author(s) of the audiobook.
     pub authors: Vec<Author>,
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     pub copyrights: Vec<Copyright>,
     /// A text description of the audiobook.
@@ -29,7 +30,7 @@ pub struct Audiobook {
     /// The narrator(s) of the audiobook.
     pub narrators: Vec<Narrator>,
     pub publisher: String,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// The amount of chapters the audiobook contains.
     pub total_chapters: u32,
@@ -46,6 +47,7 @@ pub struct SimplifiedAudiobook {
     /// The author(s) of the audiobook.
     pub authors: Vec<Author>,
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     pub copyrights: Vec<Copyright>,
     /// A text description of the audiobook.
@@ -71,7 +73,7 @@ pub struct SimplifiedAudiobook {
     pub narrators: Vec<Narrator>,
     /// The publisher of the audiobook.
     pub publisher: String,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// The amount of chapters the audiobook contains.
     pub total_chapters: Option<u32>,
@@ -95,6 +97,7 @@ pub struct Chapter {
     /// You can read more about this [here](https://developer.spotify.com/blog/2024-11-27-changes-to-the-web-api).
     pub audio_preview_url: Option<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     /// The number of the chapter in the audiobook it belongs to.
     pub chapter_number: u32,
@@ -117,8 +120,9 @@ pub struct Chapter {
     pub name: String,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
+    #[serde(default, deserialize_with = "lenient_option")]
     pub resume_point: Option<ResumePoint>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// Included in the response when a content restriction is applied.
     pub restrictions: Option<Restriction>,
@@ -140,6 +144,7 @@ pub struct SimplifiedChapter {
     /// You can read more about this [here](https://developer.spotify.com/blog/2024-11-27-changes-to-the-web-api).
     pub audio_preview_url: Option<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     /// The number of the chapter in the audiobook it belongs to.
     pub chapter_number: u32,
@@ -156,8 +161,9 @@ pub struct SimplifiedChapter {
     pub name: String,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
+    #[serde(default, deserialize_with = "lenient_option")]
     pub resume_point: Option<ResumePoint>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// Included in the response when a content restriction is applied.
     pub restrictions: Option<Restriction>,
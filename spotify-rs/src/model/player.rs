@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use spotify_rs_macros::docs;
 
 use super::{track::Track, *};
@@ -13,9 +13,9 @@ pub struct PlaybackState {
     pub repeat_state: Option<RepeatState>,
     /// Whether or not shuffle is enabled.
     pub shuffle_state: Option<bool>,
-    /// The context the item is being played from. (e.g. arist, playlist, album
-    /// or show.
-    pub context: Option<Context>,
+    /// The context the item is being played from (e.g. an artist, playlist,
+    /// album or show).
+    pub context: Option<PlaybackContext>,
     /// A Unix timestamp of when the playback state was last changed.
     pub timestamp: u64,
     /// The playback position in miliseconds.
@@ -53,6 +53,15 @@ pub struct Device {
     pub supports_volume: bool,
 }
 
+impl Device {
+    /// Whether or not the device's volume can be set, which can be used to
+    /// avoid getting an [`Error::VolumeNotSupported`](crate::Error::VolumeNotSupported)
+    /// when setting it.
+    pub fn can_set_volume(&self) -> bool {
+        self.supports_volume && !self.is_restricted
+    }
+}
+
 // Used only to deserialize JSON responses with arrays that are named objects.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub(crate) struct Devices {
@@ -62,46 +71,162 @@ pub(crate) struct Devices {
 /// The context an item is played from.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[docs]
-pub struct Context {
-    /// The type of the context (e.g. artist, playlist, album, show).
-    pub r#type: String,
-    pub href: String,
-    pub external_urls: ExternalUrls,
+pub struct PlaybackContext {
+    /// The type of the context, which determines what the URI refers to
+    /// (e.g. a playlist that can be fetched to show its name).
+    #[serde(rename = "type")]
+    pub context_type: ContextType,
+    pub href: Option<String>,
+    pub external_urls: Option<ExternalUrls>,
     pub uri: String,
 }
 
+/// The type of a [`PlaybackContext`].
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ContextType {
+    /// An album.
+    Album,
+
+    /// An artist.
+    Artist,
+
+    /// A playlist.
+    Playlist,
+
+    /// A show.
+    Show,
+
+    /// Any other context type that may be added in the future.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
 /// Allows to update the user interface based on which playback actions
 /// are currently available.
+///
+/// For example, a skip button can be disabled if [`can_skip_next`](Self::can_skip_next)
+/// returns `false`, rather than attempting to skip and handling the error.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct Actions {
     /// The disallowed actions.
     pub disallows: Disallows,
 }
 
-/// Contains (optional) disallowewd actions.
-#[derive(Clone, Debug, Deserialize, PartialEq)]
+impl Actions {
+    /// Whether or not playback can be interrupted.
+    pub fn can_interrupt_playback(&self) -> bool {
+        self.disallows.interrupting_playback != Some(true)
+    }
+
+    /// Whether or not playback can be paused.
+    pub fn can_pause(&self) -> bool {
+        self.disallows.pausing != Some(true)
+    }
+
+    /// Whether or not playback can be resumed.
+    pub fn can_resume(&self) -> bool {
+        self.disallows.resuming != Some(true)
+    }
+
+    /// Whether or not it's possible to seek to a position in the current item.
+    pub fn can_seek(&self) -> bool {
+        self.disallows.seeking != Some(true)
+    }
+
+    /// Whether or not it's possible to skip to the next item.
+    pub fn can_skip_next(&self) -> bool {
+        self.disallows.skipping_next != Some(true)
+    }
+
+    /// Whether or not it's possible to skip to the previous item (e.g. it's
+    /// not possible at the start of the context).
+    pub fn can_skip_previous(&self) -> bool {
+        self.disallows.skipping_prev != Some(true)
+    }
+
+    /// Whether or not repeating the context can be toggled.
+    pub fn can_toggle_repeat_context(&self) -> bool {
+        self.disallows.toggling_repeat_context != Some(true)
+    }
+
+    /// Whether or not repeating the current track can be toggled.
+    pub fn can_toggle_repeat_track(&self) -> bool {
+        self.disallows.toggling_repeat_track != Some(true)
+    }
+
+    /// Whether or not shuffle can be toggled.
+    pub fn can_toggle_shuffle(&self) -> bool {
+        self.disallows.toggling_shuffle != Some(true)
+    }
+
+    /// Whether or not playback can be transferred to another device.
+    pub fn can_transfer_playback(&self) -> bool {
+        self.disallows.transferring_playback != Some(true)
+    }
+}
+
+/// Contains (optional) disallowed actions. An action is disallowed if its
+/// field is `Some(true)`, and allowed otherwise.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 pub struct Disallows {
+    /// Interrupting playback.
     pub interrupting_playback: Option<bool>,
+    /// Pausing playback.
     pub pausing: Option<bool>,
+    /// Resuming playback.
     pub resuming: Option<bool>,
+    /// Seeking to a position in the current item.
     pub seeking: Option<bool>,
+    /// Skipping to the next item.
     pub skipping_next: Option<bool>,
+    /// Skipping to the previous item.
     pub skipping_prev: Option<bool>,
+    /// Toggling repeating the context.
     pub toggling_repeat_context: Option<bool>,
+    /// Toggling shuffle.
     pub toggling_shuffle: Option<bool>,
+    /// Toggling repeating the current track.
     pub toggling_repeat_track: Option<bool>,
+    /// Transferring playback to another device.
     pub transferring_playback: Option<bool>,
 }
 
 /// Represents the history entry of a played item.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "RawPlayHistory")]
 pub struct PlayHistory {
     /// The track that was played.
     pub track: Track,
     /// The date and time the track was played.
     pub played_at: DateTime<Utc>,
+    /// The date and time the track was played, exactly as returned by Spotify
+    /// (an ISO 8601 timestamp).
+    pub played_at_raw: String,
     /// The context the track was played from.
-    pub context: Option<Context>,
+    pub context: Option<PlaybackContext>,
+}
+
+// Used to keep the original `played_at` timestamp alongside the parsed one.
+#[derive(Deserialize)]
+struct RawPlayHistory {
+    track: Track,
+    played_at: String,
+    context: Option<PlaybackContext>,
+}
+
+impl TryFrom<RawPlayHistory> for PlayHistory {
+    type Error = chrono::ParseError;
+
+    fn try_from(raw: RawPlayHistory) -> Result<Self, Self::Error> {
+        Ok(Self {
+            track: raw.track,
+            played_at: raw.played_at.parse()?,
+            played_at_raw: raw.played_at,
+            context: raw.context,
+        })
+    }
 }
 
 /// A user's queue.
@@ -117,7 +242,7 @@ pub struct Queue {
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct CurrentlyPlayingItem {
     /// The context the track is being played from.
-    pub context: Option<Context>,
+    pub context: Option<PlaybackContext>,
     /// A Unix timestamp of when the playback state was last changed.
     pub timestamp: u64,
     /// The playback position in miliseconds.
@@ -159,3 +284,50 @@ pub enum CurrentlyPlayingType {
     /// An unknown item.
     Unknown,
 }
+
+/// A playback volume percentage, which is always between `0` - `100`.
+///
+/// It can be created with [`new`](Self::new) or [`try_from`](Self::try_from),
+/// which validate the volume, or with [`clamped`](Self::clamped), which clamps
+/// it to `100`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Volume(u8);
+
+impl Volume {
+    /// The maximum volume.
+    pub const MAX: Self = Self(100);
+
+    /// Create a volume, returning `None` if it's greater than `100`.
+    pub fn new(volume: u32) -> Option<Self> {
+        u8::try_from(volume).ok().filter(|v| *v <= 100).map(Self)
+    }
+
+    /// Create a volume, clamping it to `100`.
+    pub fn clamped(volume: u32) -> Self {
+        Self(volume.min(100) as u8)
+    }
+
+    /// Get the volume percentage.
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for Volume {
+    type Error = crate::error::Error;
+
+    /// Create a volume, returning an [`Error::InvalidArgument`] if it's
+    /// greater than `100`.
+    fn try_from(volume: u32) -> Result<Self, Self::Error> {
+        Self::new(volume).ok_or_else(|| crate::error::Error::InvalidArgument {
+            description: format!("The volume must be between 0 and 100, but it's {volume}."),
+        })
+    }
+}
+
+impl From<Volume> for u32 {
+    fn from(volume: Volume) -> Self {
+        volume.0.into()
+    }
+}
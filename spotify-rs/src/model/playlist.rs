@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use spotify_rs_macros::docs;
 
 use super::{user::ReferenceUser, *};
+use crate::{auth::Authorised, endpoint::playlist as endpoint};
 
 /// A playlist.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
@@ -24,14 +25,17 @@ pub struct Playlist {
     pub name: String,
     /// The owner of the playlist.
     pub owner: ReferenceUser,
+    /// The primary color of the playlist's cover, as a hex color code
+    /// (e.g. `#ffffff`), which can be used for theming. It's usually `None`.
+    pub primary_color: Option<String>,
     /// Whether or not the playlist is public (if it's added to the user's profile).
     pub public: Option<bool>,
     /// The ID for the current version of the playlist. It can be used in
     /// requests to target a specific playlist version.
-    pub snapshot_id: String,
+    pub snapshot_id: SnapshotId,
     /// The playlist's tracks.
     pub tracks: Page<PlaylistItem>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
 }
 
@@ -55,17 +59,141 @@ pub struct SimplifiedPlaylist {
     pub name: String,
     /// The owner of the playlist.
     pub owner: ReferenceUser,
+    /// The primary color of the playlist's cover, as a hex color code
+    /// (e.g. `#ffffff`), which can be used for theming. It's usually `None`.
+    pub primary_color: Option<String>,
     /// Whether or not the playlist is public (if it's added to the user's profile).
     pub public: Option<bool>,
     /// The ID for the current version of the playlist. It can be used in
     /// requests to target a specific playlist version.
-    pub snapshot_id: String,
+    pub snapshot_id: SnapshotId,
     /// The playlist's tracks.
     pub tracks: Option<TrackReference>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
 }
 
+impl Playlist {
+    /// Get a [`PlaylistSnapshot`] of this version of the playlist, which can be
+    /// used to make a sequence of changes to it.
+    pub fn snapshot(&self) -> PlaylistSnapshot {
+        PlaylistSnapshot::new(&self.id, self.snapshot_id.clone())
+    }
+}
+
+impl SimplifiedPlaylist {
+    /// Get a [`PlaylistSnapshot`] of this version of the playlist, which can be
+    /// used to make a sequence of changes to it.
+    pub fn snapshot(&self) -> PlaylistSnapshot {
+        PlaylistSnapshot::new(&self.id, self.snapshot_id.clone())
+    }
+
+    /// Get the first page of the playlist's items, as a simplified playlist
+    /// only contains a [reference](TrackReference) to them.
+    ///
+    /// To set other parameters (e.g. the market), use
+    /// [`playlist_items`](crate::endpoint::playlist::playlist_items) instead.
+    pub async fn fetch_tracks(
+        &self,
+        spotify: &Client<Token, impl AuthFlow>,
+    ) -> Result<Page<PlaylistItem>> {
+        endpoint::playlist_items(&self.id).get(spotify).await
+    }
+}
+
+/// The latest known version (snapshot) of a playlist, used to safely make a
+/// sequence of changes to it.
+///
+/// Each change is made against the current snapshot ID, which prevents
+/// clobbering changes made concurrently by someone else. After every successful
+/// change, the snapshot ID is updated to the one returned by Spotify, so the
+/// next change is made against the new version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlaylistSnapshot {
+    playlist_id: String,
+    snapshot_id: SnapshotId,
+}
+
+impl PlaylistSnapshot {
+    /// Create a new snapshot from a playlist's ID and snapshot ID.
+    pub fn new(playlist_id: impl Into<String>, snapshot_id: impl Into<SnapshotId>) -> Self {
+        Self {
+            playlist_id: playlist_id.into(),
+            snapshot_id: snapshot_id.into(),
+        }
+    }
+
+    /// The ID of the playlist.
+    pub fn playlist_id(&self) -> &str {
+        &self.playlist_id
+    }
+
+    /// The latest known snapshot ID of the playlist.
+    pub fn snapshot_id(&self) -> &SnapshotId {
+        &self.snapshot_id
+    }
+
+    /// Add one or more items to the playlist, at the given position (or at the
+    /// end, if `None`), returning the new snapshot ID.
+    pub async fn add_items<T: ToString>(
+        &mut self,
+        item_uris: &[T],
+        position: Option<u32>,
+        spotify: &Client<Token, impl AuthFlow + Authorised>,
+    ) -> Result<SnapshotId> {
+        let mut request = endpoint::add_items_to_playlist(&self.playlist_id, item_uris);
+
+        if let Some(position) = position {
+            request = request.position(position);
+        }
+
+        let snapshot_id = request.send(spotify).await?;
+        Ok(self.update(snapshot_id))
+    }
+
+    /// Remove one or more items from the playlist, returning the new snapshot ID.
+    pub async fn remove_items<T: AsRef<str>>(
+        &mut self,
+        item_uris: &[T],
+        spotify: &Client<Token, impl AuthFlow + Authorised>,
+    ) -> Result<SnapshotId> {
+        let snapshot_id = endpoint::remove_playlist_items(&self.playlist_id, item_uris)
+            .snapshot_id(self.snapshot_id.as_str())
+            .send(spotify)
+            .await?;
+
+        Ok(self.update(snapshot_id))
+    }
+
+    /// Move `range_length` items, starting at `range_start`, to the position
+    /// `insert_before`, returning the new snapshot ID.
+    ///
+    /// See [`UpdatePlaylistItemsEndpoint`](crate::endpoint::playlist::UpdatePlaylistItemsEndpoint)
+    /// for more details.
+    pub async fn reorder_items(
+        &mut self,
+        range_start: u32,
+        range_length: u32,
+        insert_before: u32,
+        spotify: &Client<Token, impl AuthFlow + Authorised>,
+    ) -> Result<SnapshotId> {
+        let snapshot_id =
+            endpoint::update_playlist_items(&self.playlist_id, range_start, insert_before)
+                .range_length(range_length)
+                .snapshot_id(self.snapshot_id.as_str())
+                .send(spotify)
+                .await?;
+
+        Ok(self.update(snapshot_id))
+    }
+
+    // Store the snapshot ID returned by a change.
+    fn update(&mut self, snapshot_id: SnapshotId) -> SnapshotId {
+        self.snapshot_id.clone_from(&snapshot_id);
+        snapshot_id
+    }
+}
+
 // Used only to deserialize JSON responses with arrays that are named objects.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub(crate) struct Playlists {
@@ -109,8 +237,64 @@ pub struct TrackReference {
     pub total: u32,
 }
 
+/// The ID of a version (snapshot) of a playlist, as returned by Spotify when
+/// a playlist is changed.
+///
+/// It's a distinct type to avoid mixing it up with a playlist ID.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SnapshotId(String);
+
+impl SnapshotId {
+    /// Create a snapshot ID, e.g. from one that was previously stored.
+    pub fn new(snapshot_id: impl Into<String>) -> Self {
+        Self(snapshot_id.into())
+    }
+
+    /// Get the snapshot ID as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for SnapshotId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for SnapshotId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SnapshotId {
+    fn from(snapshot_id: String) -> Self {
+        Self(snapshot_id)
+    }
+}
+
+impl From<&str> for SnapshotId {
+    fn from(snapshot_id: &str) -> Self {
+        Self(snapshot_id.to_owned())
+    }
+}
+
+impl From<SnapshotId> for String {
+    fn from(snapshot_id: SnapshotId) -> Self {
+        snapshot_id.0
+    }
+}
+
+impl From<&SnapshotId> for String {
+    fn from(snapshot_id: &SnapshotId) -> Self {
+        snapshot_id.0.clone()
+    }
+}
+
 // Used only to deserialize JSON responses that are named objects.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
-pub(crate) struct SnapshotId {
-    pub(crate) snapshot_id: String,
+pub(crate) struct SnapshotResponse {
+    pub(crate) snapshot_id: SnapshotId,
 }
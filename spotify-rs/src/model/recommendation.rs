@@ -12,15 +12,48 @@ pub struct Recommendations {
     pub tracks: Vec<Track>,
 }
 
+/// Information about how a seed was used to generate recommendations.
+///
+/// The pool sizes can be used to find out why a seed produced fewer
+/// recommendations than expected.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RecommendationSeed {
+    /// The number of tracks available after the `min_*`, `max_*` and `target_*`
+    /// attributes have been applied.
     pub after_filtering_size: u32,
+    /// The number of tracks available after relinking for regional availability.
     pub after_relinking_size: u32,
-    pub href: String,
+    /// A link to the full track or artist data for this seed.
+    ///
+    /// For genre seeds, this will be `None`.
+    pub href: Option<String>,
+    /// The ID used to select this seed. This will be the same as the string
+    /// used in the `seed_artists`, `seed_tracks` or `seed_genres` parameter.
     pub id: String,
+    /// The number of recommended tracks available for this seed.
     pub initial_pool_size: u32,
-    pub r#type: String,
+    /// The type of the seed.
+    pub r#type: RecommendationSeedType,
+}
+
+/// The type of a recommendation seed.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum RecommendationSeedType {
+    /// An artist seed.
+    #[serde(alias = "ARTIST")]
+    Artist,
+    /// A track seed.
+    #[serde(alias = "TRACK")]
+    Track,
+    /// A genre seed.
+    #[serde(alias = "GENRE")]
+    Genre,
+    /// Any other seed type, as more may be added in the future.
+    #[serde(other)]
+    Unknown,
 }
 
 // Used only to deserialize JSON responses with arrays that are named objects.
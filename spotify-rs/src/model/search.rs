@@ -142,6 +142,9 @@ impl Display for SearchQuery {
 
 /// The results of a search.
 ///
+/// Only the item types that were searched for are `Some`, e.g. searching only
+/// for tracks returns results with just `tracks`.
+///
 /// Note: audiobooks are only available within the US, Canada, the UK, Ireland,
 /// New Zealand and Australia.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
@@ -162,11 +165,72 @@ pub struct SearchResults {
     pub audiobooks: Option<Page<SimplifiedAudiobook>>,
 }
 
-/// An item type to search for.
+impl SearchResults {
+    /// Get all the results in a single list, interleaving the results of each
+    /// type (in the order of the fields), e.g. the first track, the first
+    /// artist, the first album, ..., then the second track and so on.
+    ///
+    /// Missing (`null`) items are skipped. The per-type pages can still be
+    /// used for type-specific pagination.
+    pub fn all_items(&self) -> Vec<SearchResultItem> {
+        fn items<T: Clone>(
+            page: &Option<Page<T>>,
+            f: fn(T) -> SearchResultItem,
+        ) -> Vec<SearchResultItem> {
+            page.iter()
+                .flat_map(|p| p.items.iter().flatten().cloned().map(f))
+                .collect()
+        }
+
+        let mut sections = [
+            items(&self.tracks, |t| SearchResultItem::Track(Box::new(t))),
+            items(&self.artists, SearchResultItem::Artist),
+            items(&self.albums, SearchResultItem::Album),
+            items(&self.playlists, SearchResultItem::Playlist),
+            items(&self.shows, SearchResultItem::Show),
+            items(&self.episodes, SearchResultItem::Episode),
+            items(&self.audiobooks, SearchResultItem::Audiobook),
+        ]
+        .map(Vec::into_iter);
+
+        let mut results = vec![];
+
+        loop {
+            let len = results.len();
+            results.extend(sections.iter_mut().filter_map(Iterator::next));
+
+            if results.len() == len {
+                return results;
+            }
+        }
+    }
+}
+
+/// A single search result, of any type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchResultItem {
+    /// A track result.
+    Track(Box<Track>),
+    /// An artist result.
+    Artist(Artist),
+    /// An album result.
+    Album(SimplifiedAlbum),
+    /// A playlist result.
+    Playlist(SimplifiedPlaylist),
+    /// A show result.
+    Show(SimplifiedShow),
+    /// An episode result.
+    Episode(SimplifiedEpisode),
+    /// An audiobook result.
+    Audiobook(SimplifiedAudiobook),
+}
+
+/// An item type to search for, passed to
+/// [`search`](crate::endpoint::search::search).
 ///
 /// You can either use [all](Self::all()) to get a list of all types of items,
 /// or construct a list yourself to use in your search.
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Item {
     /// Album type.
     Album,
@@ -9,6 +9,7 @@ use super::*;
 #[docs]
 pub struct Show {
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     pub copyrights: Vec<String>,
     pub description: String,
@@ -21,11 +22,12 @@ pub struct Show {
     /// Whether or not all of the show's episodes are hosted outside of Spotify's
     /// CDN.
     pub is_externally_hosted: Option<bool>,
+    #[serde(default, deserialize_with = "null_to_default")]
     pub languages: Vec<String>,
     pub media_type: String,
     pub name: String,
     pub publisher: String,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// The amount of episodes the show contains.
     pub total_episodes: u32,
@@ -33,6 +35,17 @@ pub struct Show {
     pub episodes: Page<SimplifiedEpisode>,
 }
 
+impl Show {
+    /// Whether or not the show is in the given language, as an
+    /// [ISO 639-1](https://en.wikipedia.org/wiki/ISO_639-1) code (e.g. `en`),
+    /// optionally with a region (e.g. `en-US`).
+    ///
+    /// A language without a region matches every region of that language.
+    pub fn supports_language(&self, language: &str) -> bool {
+        supports_language(&self.languages, language)
+    }
+}
+
 /// A simplified show, missing some details, that is usually obtained
 /// through endpoints not specific to shows. The `href` field may be
 /// used to get a full show.
@@ -40,6 +53,7 @@ pub struct Show {
 #[docs(name = "show")]
 pub struct SimplifiedShow {
     #[serde(default)]
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Vec<String>,
     pub copyrights: Vec<String>,
     pub description: String,
@@ -52,16 +66,37 @@ pub struct SimplifiedShow {
     /// Whether or not all of the show's episodes are hosted outside of Spotify's
     /// CDN.
     pub is_externally_hosted: Option<bool>,
+    #[serde(default, deserialize_with = "null_to_default")]
     pub languages: Vec<String>,
     pub media_type: String,
     pub name: String,
     pub publisher: String,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// The amount of episodes the show contains.
     pub total_episodes: u32,
 }
 
+impl SimplifiedShow {
+    /// Whether or not the show is in the given language. Read more in
+    /// [`Show::supports_language`].
+    pub fn supports_language(&self, language: &str) -> bool {
+        supports_language(&self.languages, language)
+    }
+}
+
+// Check if any of the languages matches the given one, where a language
+// without a region (e.g. `en`) matches all of its regions (e.g. `en-US`).
+fn supports_language(languages: &[String], language: &str) -> bool {
+    languages.iter().any(|l| {
+        l.eq_ignore_ascii_case(language)
+            || (!language.contains('-')
+                && l.split('-')
+                    .next()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(language)))
+    })
+}
+
 /// A show saved by a user.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct SavedShow {
@@ -98,13 +133,19 @@ pub struct Episode {
     pub images: Vec<Image>,
     /// Whether or not the episode is hosted outside of Spotify's CDN.
     pub is_externally_hosted: bool,
-    pub is_playable: bool,
+    /// Whether or not the episode is playable in the given market.
+    ///
+    /// It may be `None` if Spotify doesn't include it in the response. If the
+    /// episode isn't playable, the reason may be found in `restrictions`.
+    pub is_playable: Option<bool>,
+    #[serde(default, deserialize_with = "null_to_default")]
     pub languages: Vec<String>,
     pub name: String,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
+    #[serde(default, deserialize_with = "lenient_option")]
     pub resume_point: Option<ResumePoint>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// Included in the response when a content restriction is applied.
     pub restrictions: Option<Restriction>,
@@ -135,13 +176,19 @@ pub struct SimplifiedEpisode {
     pub images: Vec<Image>,
     /// Whether or not the episode is hosted outside of Spotify's CDN.
     pub is_externally_hosted: bool,
-    pub is_playable: bool,
+    /// Whether or not the episode is playable in the given market.
+    ///
+    /// It may be `None` if Spotify doesn't include it in the response. If the
+    /// episode isn't playable, the reason may be found in `restrictions`.
+    pub is_playable: Option<bool>,
+    #[serde(default, deserialize_with = "null_to_default")]
     pub languages: Vec<String>,
     pub name: String,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
+    #[serde(default, deserialize_with = "lenient_option")]
     pub resume_point: Option<ResumePoint>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// Included in the response when a content restriction is applied.
     pub restrictions: Option<Restriction>,
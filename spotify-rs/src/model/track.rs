@@ -1,3 +1,5 @@
+use std::{borrow::Borrow, time::Duration};
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use spotify_rs_macros::docs;
@@ -12,6 +14,7 @@ pub struct Track {
     pub album: SimplifiedAlbum,
     /// The artists who performed on the track.
     pub artists: Vec<SimplifiedArtist>,
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Option<Vec<String>>,
     /// The disc number, which us usually `1`, unless the album consists of more
     /// than one disk.
@@ -22,9 +25,7 @@ pub struct Track {
     pub external_urls: ExternalUrls,
     pub href: String,
     pub id: String,
-    /// It's part of the response when
-    /// [Track Relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
-    /// is applied.
+    /// See [`Playable::playable`].
     pub is_playable: Option<bool>,
     /// It's part of the response when
     /// [Track Relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
@@ -43,7 +44,10 @@ pub struct Track {
     ///
     /// Note: the value may lag behind by a few days, as it's not updated in
     /// real time.
-    pub popularity: u32,
+    ///
+    /// It's `None` if Spotify doesn't include it in the response, which is
+    /// different from a popularity of `0`.
+    pub popularity: Option<u8>,
     /// The URL for a 30 second MP3 preview of the track.
     ///
     /// **Note:** This attribute has been deprecated by Spotify. It continues to work for
@@ -53,7 +57,7 @@ pub struct Track {
     pub preview_url: Option<String>,
     /// The number of the track.
     pub track_number: u32,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// Whether or not the track is from a local file.
     pub is_local: bool,
@@ -62,7 +66,7 @@ pub struct Track {
 // Used only to deserialize JSON responses with arrays that are named objects.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub(crate) struct Tracks {
-    pub(crate) tracks: Vec<Track>,
+    pub(crate) tracks: Vec<Option<Track>>,
 }
 
 /// A simplified track, missing some details, that is usually obtained
@@ -73,6 +77,7 @@ pub(crate) struct Tracks {
 pub struct SimplifiedTrack {
     /// The artists who performed on the track.
     pub artists: Vec<SimplifiedArtist>,
+    #[cfg_attr(feature = "skip-available-markets", serde(skip_deserializing))]
     pub available_markets: Option<Vec<String>>,
     /// The disc number, which us usually `1`, unless the album consists of more
     /// than one disk.
@@ -82,6 +87,7 @@ pub struct SimplifiedTrack {
     pub external_urls: ExternalUrls,
     pub href: String,
     pub id: String,
+    /// See [`Playable::playable`].
     pub is_playable: Option<bool>,
     /// It's part of the response when
     /// [Track Relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
@@ -100,12 +106,108 @@ pub struct SimplifiedTrack {
     pub preview_url: Option<String>,
     /// The number of the track.
     pub track_number: u32,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// Whether or not the track is from a local file.
     pub is_local: bool,
 }
 
+impl Track {
+    /// Get a [`SimplifiedTrack`] view of the track, which doesn't include
+    /// the album, external IDs and popularity.
+    pub fn simplified(&self) -> SimplifiedTrack {
+        self.clone().into()
+    }
+
+    /// Get the ID of the originally requested track, if this track has been
+    /// [relinked](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
+    /// to a different one (e.g. one available in the requested market).
+    ///
+    /// Returns `None` if the track hasn't been relinked.
+    pub fn original_id(&self) -> Option<&str> {
+        self.linked_from.as_ref()?.id.as_deref()
+    }
+
+    /// Get the duration of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+
+    /// Get the names of the track's artists, separated by ", " (e.g.
+    /// `Artist A, Artist B`).
+    pub fn artists_string(&self) -> String {
+        artist::join_names(&self.artists)
+    }
+
+    /// Get the primary (first listed) artist of the track.
+    pub fn primary_artist(&self) -> Option<&SimplifiedArtist> {
+        self.artists.first()
+    }
+}
+
+impl SimplifiedTrack {
+    /// Get the ID of the originally requested track, if this track has been
+    /// [relinked](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
+    /// to a different one (e.g. one available in the requested market).
+    ///
+    /// Returns `None` if the track hasn't been relinked.
+    pub fn original_id(&self) -> Option<&str> {
+        self.linked_from.as_ref()?.id.as_deref()
+    }
+
+    /// Get the duration of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+
+    /// Get the names of the track's artists, separated by ", " (e.g.
+    /// `Artist A, Artist B`).
+    pub fn artists_string(&self) -> String {
+        artist::join_names(&self.artists)
+    }
+
+    /// Get the primary (first listed) artist of the track.
+    pub fn primary_artist(&self) -> Option<&SimplifiedArtist> {
+        self.artists.first()
+    }
+}
+
+impl From<Track> for SimplifiedTrack {
+    fn from(track: Track) -> Self {
+        Self {
+            artists: track.artists,
+            available_markets: track.available_markets,
+            disc_number: track.disc_number,
+            duration_ms: track.duration_ms,
+            explicit: track.explicit,
+            external_urls: track.external_urls,
+            href: track.href,
+            id: track.id,
+            is_playable: track.is_playable,
+            linked_from: track.linked_from,
+            restrictions: track.restrictions,
+            name: track.name,
+            preview_url: track.preview_url,
+            track_number: track.track_number,
+            r#type: track.r#type,
+            uri: track.uri,
+            is_local: track.is_local,
+        }
+    }
+}
+
+/// Get the total duration of the given tracks, e.g. to show the length of a
+/// playlist or album.
+pub fn total_duration<T: Borrow<Track>>(tracks: impl IntoIterator<Item = T>) -> Duration {
+    // Summed as `u64`, so even very long lists of tracks can't overflow.
+    let total_ms: u64 = tracks
+        .into_iter()
+        .map(|t| u64::from(t.borrow().duration_ms))
+        .sum();
+
+    Duration::from_millis(total_ms)
+}
+
 /// A track saved by a user.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct SavedTrack {
@@ -123,6 +225,6 @@ pub struct LinkedFrom {
     pub external_urls: ExternalUrls,
     pub href: String,
     pub id: Option<String>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
 }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use spotify_rs_macros::docs;
+use strum::IntoStaticStr;
 
 use super::*;
 
@@ -13,7 +14,7 @@ pub struct PrivateUser {
     ///
     /// Note: this field is only available if the user is authorised with the
     /// `user-read-private` scope.
-    pub country: String,
+    pub country: Option<String>,
     /// The name that is displayed on the user's profile.
     pub display_name: Option<String>,
     /// The user's email address.
@@ -21,7 +22,7 @@ pub struct PrivateUser {
     /// Note: this email address is *unverified*, meaning that there is no proof
     /// that it actually belongs to the user; this field is only available if the
     /// user is authorised with the `user-read-email` scope.
-    pub email: String,
+    pub email: Option<String>,
     /// The user's explicit content settings.
     ///
     /// Note: This field is only available if the user is authorised with the
@@ -39,10 +40,32 @@ pub struct PrivateUser {
     /// Note: This field is only available if the user is authorised with the
     /// `user-read-private` scope.
     pub product: Option<String>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
 }
 
+impl PrivateUser {
+    /// Whether or not the user has Spotify Premium, which is required to
+    /// control playback.
+    ///
+    /// Returns `None` if the subscription tier is unknown, which is the case
+    /// unless the user is authorised with the `user-read-private` scope.
+    pub fn is_premium(&self) -> Option<bool> {
+        self.product.as_deref().map(|p| p == "premium")
+    }
+
+    /// Whether or not the user's settings filter out explicit content, in which
+    /// case explicit items (see [`Explicit`]) shouldn't be played.
+    ///
+    /// Returns `false` if the explicit content settings are unknown, which is
+    /// the case unless the user is authorised with the `user-read-private` scope.
+    pub fn filters_explicit_content(&self) -> bool {
+        self.explicit_content
+            .as_ref()
+            .is_some_and(|e| e.filter_enabled)
+    }
+}
+
 /// A user.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[docs]
@@ -55,7 +78,7 @@ pub struct User {
     pub href: String,
     pub id: String,
     pub images: Vec<Image>,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
 }
 
@@ -68,16 +91,27 @@ pub struct ReferenceUser {
     pub external_urls: ExternalUrls,
     pub href: String,
     pub id: String,
-    pub r#type: String,
+    pub r#type: ObjectType,
     pub uri: String,
     /// The name that is displayed on the user's profile.
     pub display_name: Option<String>,
 }
 
+impl ReferenceUser {
+    /// Get the user's full public profile, which includes their followers and
+    /// images.
+    ///
+    /// This will make a request to get the user using their ID.
+    pub async fn fetch_full(&self, spotify: &Client<Token, impl AuthFlow>) -> Result<User> {
+        crate::endpoint::user::get_user(&self.id, spotify).await
+    }
+}
+
 /// A user's explicit content settings.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct ExplicitContent {
-    /// Whether or not explicit content should be played.
+    /// Whether or not explicit content is filtered out, meaning it shouldn't
+    /// be played.
     pub filter_enabled: bool,
     /// Whether or not the explicit content setting is locked and
     /// can't be modified by the user.
@@ -85,8 +119,12 @@ pub struct ExplicitContent {
 }
 
 /// Over what timespan the top items are calculated.
-#[derive(Clone, Debug, Default, Serialize)]
+///
+/// It's serialized as, and can be converted into, the value used by Spotify's
+/// `time_range` query parameter (e.g. `medium_term`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, IntoStaticStr)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum TimeRange {
     /// Calculated from the last ~1 year of data.
     LongTerm,